@@ -0,0 +1,26 @@
+#include <stdlib.h>
+
+struct node {
+  int value;
+  struct node *next;
+};
+
+// With `--warn-alloc-size`, the `char` and `struct node *` sizes are flagged
+void allocate_wrong(int n) {
+  int *values = malloc(n * sizeof(char));
+  struct node *head;
+  head = (struct node *)calloc(1, sizeof(struct node *));
+  free(values);
+  free(head);
+}
+
+// Sizes of the pointed-to type, or of what the pointer points at, are fine
+void allocate_right(int n) {
+  int *values = malloc(n * sizeof(int));
+  struct node **nodes = malloc(n * sizeof(struct node *));
+  struct node *head;
+  head = malloc(sizeof *head);
+  free(values);
+  free(nodes);
+  free(head);
+}
@@ -0,0 +1,13 @@
+#include <stdio.h>
+
+// `scores` and `names` look like arrays but are pointers inside the function
+void print_scores(int scores[10], char *names[], int count) {
+  for (int i = 0; i < count; i++) {
+    printf("%s %d\n", names[i], scores[i]);
+  }
+}
+
+// Pointers, including a pointer to rows of an array, are declared as what they are
+void print_row(int (*grid)[3], int *values, int row) {
+  printf("%d %d\n", grid[row][0], values[row]);
+}
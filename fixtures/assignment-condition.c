@@ -0,0 +1,26 @@
+#include <stdio.h>
+
+// Assigning instead of comparing in an if, while, or for condition
+void count(int n) {
+  int i;
+  if (n = 0) {
+    return;
+  }
+  while (n = 1) {
+    n--;
+  }
+  for (i = 0; i = n; i++) {
+    printf("%d\n", i);
+  }
+}
+
+// Comparisons, and assignments wrapped in extra parentheses, are fine
+void echo(void) {
+  int c;
+  while ((c = getchar()) != EOF) {
+    putchar(c);
+  }
+  if ((c = getchar())) {
+    putchar(c);
+  }
+}
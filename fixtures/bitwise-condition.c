@@ -0,0 +1,30 @@
+#define FLAG_READY 4
+
+// Bitwise operators making up a whole condition are flagged
+int check(int ready, int done, int flags) {
+  if (ready & done) {
+    return 1;
+  }
+  while (ready | done) {
+    ready = 0;
+    done = 0;
+  }
+  for (; ready & done;) {
+    ready = 0;
+  }
+  return 0;
+}
+
+// Masks, comparisons, and bitwise values outside conditions are left alone
+int masks(int flags, int other) {
+  if (flags & 1) {
+    return 1;
+  }
+  if (flags & FLAG_READY) {
+    return 2;
+  }
+  if ((flags & other) == 0) {
+    return 3;
+  }
+  return flags | other;
+}
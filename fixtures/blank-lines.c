@@ -0,0 +1,10 @@
+#include <stdio.h>
+
+
+
+// Entry point
+int main() {
+  return 0;
+}
+
+
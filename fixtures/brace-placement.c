@@ -0,0 +1,15 @@
+#include <stdio.h>
+
+// Most braces here are on the same line, so the two on their own line are flagged
+void count_down(int n)
+{
+  while (n > 0) {
+    if (n % 2 == 0)
+    {
+      printf("%d\n", n);
+    } else {
+      puts("odd");
+    }
+    n--;
+  }
+}
@@ -0,0 +1,7 @@
+#include <stdio.h>
+
+// Entry point, with a blank line before the function
+
+int main() {
+  return 0;
+}
@@ -0,0 +1,15 @@
+#include <stdio.h>
+
+// Print a greeting, the old version is still below
+void greet(const char *name) {
+  // printf("Hello\n");
+  printf("Hello, %s\n", name);
+  /*
+   * if (name == NULL) {
+   *   return;
+   * }
+   */
+  // make sure the name is printed; then flush
+  // TODO: handle long names
+  fflush(stdout);
+}
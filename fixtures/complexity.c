@@ -0,0 +1,26 @@
+#include <stdio.h>
+
+// Classify a character, with far too many branches
+int classify(char c, int strict) {
+  if (c >= 'a' && c <= 'z') {
+    return 1;
+  } else if (c >= 'A' && c <= 'Z') {
+    return 2;
+  }
+
+  switch (c) {
+  case ' ':
+  case '\t':
+    return 3;
+  default:
+    break;
+  }
+
+  for (int i = 0; i < 3 || strict; i++) {
+    while (strict) {
+      strict--;
+    }
+  }
+
+  return strict ? -1 : 0;
+}
@@ -0,0 +1,9 @@
+#include <stdio.h>
+
+// The marker below is reported, the global is not
+int total = 0;
+
+// TODO: remove this file
+void report(void) {
+  printf("%d\n", total);
+}
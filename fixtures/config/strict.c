@@ -0,0 +1,10 @@
+#include <stdio.h>
+
+// The global is reported, the marker below is not
+int counter = 0;
+
+// TODO: count something
+int main(void) {
+  printf("%d\n", counter);
+  return 0;
+}
@@ -0,0 +1,20 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+// Compare two integers for qsort, only ever passed as a pointer
+int compare(const void *a, const void *b) {
+  return *(const int *)a - *(const int *)b;
+}
+
+// Never called from anywhere, so it is flagged
+int *unused(void) {
+  return NULL;
+}
+
+// Entry point, never flagged
+int main() {
+  int values[] = {3, 1, 2};
+  qsort(values, 3, sizeof(int), compare);
+  printf("%d\n", values[0]);
+  return 0;
+}
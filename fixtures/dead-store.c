@@ -0,0 +1,32 @@
+#include <stdio.h>
+
+// With `--warn-dead-stores`, the first values of `total` and `sum` are never read
+void report(int n) {
+  int total = 0;
+  int sum;
+  total = n * 2;
+  sum = 1;
+  sum = total + 1;
+  printf("sum %d\n", sum);
+}
+
+// A read in between, or a possible read in a branch, keeps the earlier value alive
+void count_up(int n) {
+  int count = n;
+  printf("count %d\n", count);
+  count = 0;
+  if (n > 0) {
+    printf("positive %d\n", count);
+  }
+  count = 1;
+  printf("final %d\n", count);
+}
+
+// A variable whose address is taken may be read through the pointer
+void through_pointer(void) {
+  int value = 0;
+  int *pointer = &value;
+  value = 1;
+  value = 2;
+  printf("value %d\n", *pointer);
+}
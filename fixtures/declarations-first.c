@@ -0,0 +1,17 @@
+#include <stdio.h>
+
+// Declarations after the first statement of a block are flagged
+int total(int count) {
+  int sum = 0;
+  // comments between declarations are fine
+  int i;
+  printf("summing\n");
+  int late = 1;
+  for (i = 0; i < count; i++) {
+    int square = i * i;
+    sum += square;
+    int doubled = sum * 2;
+    sum = doubled / 2;
+  }
+  return sum + late;
+}
@@ -0,0 +1,24 @@
+#include <stdio.h>
+
+// Exactly 10 lines, the compact do/while is a single line and only counted once
+void compact(int n) {
+  int i = 0;
+  do { i++; } while (i < n);
+  i = i + 1;
+  i = i + 2;
+  i = i + 3;
+  i = i + 4;
+  i = i + 5;
+  i = i + 6;
+  i = i + 7;
+  printf("%d\n", i);
+}
+
+// The `} while` line holds only the condition, so it still counts as its own line
+void spread(int n) {
+  int i = 0;
+  do {
+    i++;
+  } while (i < n);
+  printf("%d\n", i);
+}
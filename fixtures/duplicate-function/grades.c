@@ -0,0 +1,14 @@
+#include "grades.h"
+
+// Keeps a score between 0 and 100
+static int clamp(int score) {
+  if (score < 0) {
+    return 0;
+  }
+  return score > 100 ? 100 : score;
+}
+
+// Averages two scores
+int average(int first, int second) {
+  return (clamp(first) + clamp(second)) / 2;
+}
@@ -0,0 +1,6 @@
+#ifndef GRADES_H
+#define GRADES_H
+
+int average(int first, int second);
+
+#endif
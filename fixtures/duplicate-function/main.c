@@ -0,0 +1,22 @@
+#include <stdio.h>
+
+#include "grades.h"
+
+// Keeps a score between 0 and 100, separate from the one in grades.c
+static int clamp(int score) {
+  if (score < 0) {
+    return 0;
+  }
+  return score > 100 ? 100 : score;
+}
+
+// Copied from grades.c, which already defines it
+int average(int first, int second) {
+  return (clamp(first) + clamp(second)) / 2;
+}
+
+// Prints the average of two scores
+int main(void) {
+  printf("%d\n", average(90, 80));
+  return 0;
+}
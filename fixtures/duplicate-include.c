@@ -0,0 +1,11 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <stdio.h>
+#include <string.h>
+
+// Only the second and later includes of a path are flagged
+int main(void) {
+  printf("%d\n", (int)strlen("x") + EXIT_SUCCESS);
+  return 0;
+}
@@ -0,0 +1,7 @@
+#include "./util.h"
+#include "util.h"
+
+// Entry point, util.h is only linted once
+int main() {
+  return item_count;
+}
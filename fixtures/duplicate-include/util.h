@@ -0,0 +1,3 @@
+#define MAX_ITEMS 16
+
+int item_count = 0;
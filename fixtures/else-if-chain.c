@@ -0,0 +1,33 @@
+#include <stdio.h>
+
+#define QUIT 'q'
+
+// Five branches comparing `key` against constants, this is flagged
+void command(char key) {
+  if (key == 'a') {
+    puts("add");
+  } else if (key == 'd') {
+    puts("delete");
+  } else if ('l' == key) {
+    puts("list");
+  } else if (key == 's') {
+    puts("save");
+  } else if (key == QUIT) {
+    puts("quit");
+  }
+}
+
+// The chain switches variables halfway, so it is left alone
+void mixed(int x, int y) {
+  if (x == 1) {
+    puts("one");
+  } else if (x == 2) {
+    puts("two");
+  } else if (y == 3) {
+    puts("three");
+  } else if (x == 4) {
+    puts("four");
+  } else if (x == 5) {
+    puts("five");
+  }
+}
@@ -0,0 +1,13 @@
+// Prototypes and definitions with an empty parameter list are flagged
+int unspecified();
+int explicit_void(void);
+
+// Takes any arguments at all
+int unspecified() {
+  return 0;
+}
+
+// Takes no arguments
+int explicit_void(void) {
+  return unspecified();
+}
@@ -0,0 +1,10 @@
+// Each stray semicolon is flagged, the semicolons in a for loop header are not
+int stray(int x) {
+  if (x > 0);
+  while (x > 10);
+  x++;;
+  for (;;) {
+    break;
+  }
+  return x;
+}
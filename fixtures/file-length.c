@@ -0,0 +1,25 @@
+#include <stdio.h>
+
+// Over 20 lines, run with `--max-file-lines 20`
+
+// Adds two numbers
+int add(int a, int b) {
+  return a + b;
+}
+
+// Subtracts two numbers
+int subtract(int a, int b) {
+  return a - b;
+}
+
+// Multiplies two numbers
+int multiply(int a, int b) {
+  return a * b;
+}
+
+// Prints a few results
+int main(void) {
+  printf("%d %d %d\n", add(1, 2), subtract(5, 3), multiply(2, 4));
+  return 0;
+}
+
@@ -0,0 +1,16 @@
+#include <stdio.h>
+
+// Check a few values against their expected results
+int check(double ratio, int count) {
+  float half = 0.5f;
+  if (ratio == 0.1) {
+    printf("Exactly a tenth\n");
+  }
+  if (half != ratio) {
+    printf("Not a half\n");
+  }
+  if (count == 3) {
+    printf("Integers are fine\n");
+  }
+  return 1.0 == count;
+}
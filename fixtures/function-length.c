@@ -0,0 +1,32 @@
+#include <stdio.h>
+
+// Exactly 10 lines, the braces on their own lines do not count, so this is not flagged
+void ten_lines(int x)
+{
+  x = x + 1;
+  x = x + 2;
+  x = x + 3;
+  x = x + 4;
+  x = x + 5;
+  x = x + 6;
+  x = x + 7;
+  x = x + 8;
+  x = x + 9;
+  printf("%d\n", x);
+}
+
+// One statement too many, this is flagged with 11 lines
+void eleven_lines(int x)
+{
+  x = x + 1;
+  x = x + 2;
+  x = x + 3;
+  x = x + 4;
+  x = x + 5;
+  x = x + 6;
+  x = x + 7;
+  x = x + 8;
+  x = x + 9;
+  x = x + 10;
+  printf("%d\n", x);
+}
@@ -0,0 +1,18 @@
+#include <stdio.h>
+
+// prototypes are not global variables
+int square(int x);
+int *make_buffer(void);
+
+// typedefs and bare struct definitions are not either
+typedef int (*binary_op)(int, int);
+struct point {
+  int x;
+};
+
+// but each of these is
+int plain_global = 0;
+int *pointer_global;
+int array_global[4];
+int (*function_pointer_global)(int);
+int first_of_many, second_of_many;
@@ -0,0 +1,6 @@
+#include "shapes.h"
+
+// Area of a rectangle
+int area(int width, int height) {
+  return twice(half(width * height));
+}
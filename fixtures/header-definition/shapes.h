@@ -0,0 +1,22 @@
+#ifndef SHAPES_H
+#define SHAPES_H
+
+// Declared here and defined in shapes.c
+int area(int width, int height);
+
+// Defined in every file that includes this header
+int perimeter(int width, int height) {
+  return 2 * (width + height);
+}
+
+// Each file gets its own private copy, so these are fine
+static int half(int value) {
+  return value / 2;
+}
+
+// Inline definitions are meant to live in headers
+inline int twice(int value) {
+  return value * 2;
+}
+
+#endif
@@ -0,0 +1,10 @@
+#define maxCount 10
+
+// With --identifier-style camel the snake_case names are flagged, with snake the camelCase ones
+int sum_values(int *values, int valueCount) {
+  int total_sum = 0;
+  for (int i = 0; i < valueCount && i < maxCount; i++) {
+    total_sum += values[i];
+  }
+  return total_sum;
+}
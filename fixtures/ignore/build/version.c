@@ -0,0 +1 @@
+int build_number = 42;
@@ -0,0 +1,10 @@
+#include <stdio.h>
+
+#include "vendor/course.h"
+#include "vendor/third_party.h"
+
+// Prints the score limit
+int main(void) {
+  printf("%d\n", max_scores);
+  return 0;
+}
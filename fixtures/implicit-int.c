@@ -0,0 +1,7 @@
+#include <stdio.h>
+
+// Entry point, written in old-style C
+main() {
+  printf("Hello\n");
+  return 0;
+}
@@ -0,0 +1,7 @@
+#ifndef COUNTER_H
+#define COUNTER_H
+
+// Returns how many times it has been called
+int next_count(void);
+
+#endif
@@ -0,0 +1,10 @@
+#include <stdio.h>
+
+// Only found with `--include-path fixtures/include-path/include`
+#include "counter.h"
+
+// Prints the first count
+int main(void) {
+  printf("%d\n", next_count());
+  return 0;
+}
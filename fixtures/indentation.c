@@ -0,0 +1,12 @@
+#include <stdio.h>
+
+// Print a number, indented mostly with tabs
+void show(int x) {
+	if (x) {
+		printf("%d\n", x);
+    }
+	/*
+	 * aligned comment
+	 */
+	 	return;
+}
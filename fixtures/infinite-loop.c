@@ -0,0 +1,32 @@
+#include <stdio.h>
+
+// Print the numbers below a limit, in a few different ways
+void print_below(int n) {
+  int i = 0;
+  while (i < n) {
+    printf("%d\n", i);
+  }
+
+  for (int j = 0; j < n;) {
+    printf("%d\n", j);
+  }
+
+  for (int j = 0; j < n; j++) {
+    printf("%d\n", j);
+  }
+
+  while (i < n) {
+    printf("%d\n", i++);
+  }
+
+  while (i > 0) {
+    if (i == n) {
+      break;
+    }
+  }
+
+  int c;
+  while ((c = getchar()) != EOF) {
+    putchar(c);
+  }
+}
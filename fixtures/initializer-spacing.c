@@ -0,0 +1,19 @@
+#include <stdio.h>
+
+struct point {
+  int x;
+  int y;
+};
+
+// Prints a few squares and a point
+int main(void) {
+  int squares[] = {1,4, 9 , 16, 25,};
+  int cubes[] = {
+      1,
+      8,
+      27,
+  };
+  struct point origin = {0,0};
+  printf("%d %d %d %d\n", squares[1], cubes[2], origin.x, origin.y);
+  return 0;
+}
@@ -0,0 +1,26 @@
+// Each integer division stored as a floating-point value truncates first
+double average(int sum, int count) {
+  double mean = sum / count;
+  float half;
+  half = (sum / 2);
+  return mean + half;
+}
+
+// Returning an integer division from a function returning a double truncates too
+double fraction(int part, int whole) {
+  return part / whole;
+}
+
+// Divisions with a floating-point operand, or stored as an integer, are left alone
+double ratio(int a, int b, double scale) {
+  double exact = (double)a / b;
+  double scaled = scale / a;
+  int whole = a / b;
+  double literal = a / 2.0;
+  return whole + exact + scaled + literal;
+}
+
+// Returning the division from a function returning an integer is fine
+int quotient(int a, int b) {
+  return a / b;
+}
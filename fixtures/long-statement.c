@@ -0,0 +1,14 @@
+#include <stdio.h>
+
+// The call spans 6 lines, one more than the default limit
+void report(int a, int b, int c) {
+  printf("%d %d %d %d %d\n",
+         a,
+         b,
+         c,
+         a + b + c,
+         a * b * c);
+  int total = a + b +
+              c;
+  printf("%d\n", total);
+}
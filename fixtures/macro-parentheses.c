@@ -0,0 +1,15 @@
+#include <stdio.h>
+
+#define SQUARE(x) x * x
+#define DOUBLE(x) (x + x)
+#define HALF(x) ((x) / 2)
+#define MAX(a, b) ((a) > (b) ? (a) : (b))
+#define PRINT_INT(x) printf("%d\n", (x))
+#define STRINGIFY(x) #x
+#define CONCAT(a, b) a##b
+#define SWAP(a, b)                                                             \
+  do {                                                                         \
+    int tmp = a;                                                               \
+    a = b;                                                                     \
+    b = tmp;                                                                   \
+  } while (0)
@@ -0,0 +1,6 @@
+#include <stdio.h>
+
+// Prints a greeting, but returns no exit status
+void main(void) {
+  printf("Hello\n");
+}
@@ -0,0 +1,32 @@
+#include <stdlib.h>
+
+struct node {
+  int value;
+  struct node *next;
+};
+
+// Allocates and never frees, this is flagged
+int leaky(int count) {
+  int *values = malloc(count * sizeof(int));
+  values[0] = count;
+  return values[0];
+}
+
+// Frees what it allocates
+int tidy(int count) {
+  int *values = calloc(count, sizeof(int));
+  int first = values[0];
+  free(values);
+  return first;
+}
+
+// Returns the allocation to the caller
+int *make(int count) {
+  return malloc(count * sizeof(int));
+}
+
+// Stores the allocation in a structure that outlives the function
+void push(struct node *head, int value) {
+  head->next = (struct node *)malloc(sizeof(struct node));
+  head->next->value = value;
+}
@@ -0,0 +1,27 @@
+#include <stdio.h>
+
+// A break outside any loop or switch, and a continue in a switch but no loop
+void classify(int value) {
+  if (value < 0) {
+    break;
+  }
+  switch (value) {
+  case 0:
+    continue;
+  default:
+    break;
+  }
+}
+
+// Jumps inside a loop are fine, even from a switch in the loop
+void count(int limit) {
+  for (int i = 0; i < limit; i++) {
+    switch (i % 2) {
+    case 0:
+      continue;
+    default:
+      break;
+    }
+    printf("%d\n", i);
+  }
+}
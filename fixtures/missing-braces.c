@@ -0,0 +1,34 @@
+// Every unbraced branch is flagged, `else if` is not
+int branches(int x) {
+  if (x > 10)
+    x = 10;
+  else if (x < 0)
+    x = 0;
+  else
+    x++;
+  return x;
+}
+
+// Every unbraced loop body is flagged
+int loops(int x) {
+  while (x > 5)
+    x--;
+  for (int i = 0; i < 3; i++)
+    x += i;
+  do
+    x++;
+  while (x < 3);
+  return x;
+}
+
+// Braced bodies are fine
+int braced(int x) {
+  if (x > 10) {
+    x = 10;
+  } else if (x < 0) {
+    x = 0;
+  } else {
+    x++;
+  }
+  return x;
+}
@@ -0,0 +1,8 @@
+#include <stdio.h>
+#include "util.h"
+
+// main never needs a prototype
+int main(void) {
+  printf("%s %d %d\n", name(), add(1, 2), subtract(3, 1));
+  return 0;
+}
@@ -0,0 +1,21 @@
+#include "util.h"
+
+// Declared in util.h
+int add(int a, int b) {
+  return a + b;
+}
+
+// Declared in util.h, returning a pointer
+char *name(void) {
+  return "util";
+}
+
+// Not declared anywhere, this is flagged
+int subtract(int a, int b) {
+  return a - b;
+}
+
+// Static functions are private to this file and need no prototype
+static int twice(int a) {
+  return add(a, a);
+}
@@ -0,0 +1,7 @@
+#ifndef UTIL_H
+#define UTIL_H
+
+int add(int a, int b);
+char *name(void);
+
+#endif
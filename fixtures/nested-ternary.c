@@ -0,0 +1,13 @@
+// Ternaries nested in either branch are flagged, with or without parentheses
+int sign(int x) {
+  int magnitude = x < 0 ? -x : x;
+  int clamped = x > 100 ? 100 : (x < -100 ? -100 : x);
+  return x > 0 ? 1
+       : x < 0 ? -1
+               : magnitude + clamped;
+}
+
+// A ternary in the condition of another is left alone
+int pick(int a, int b) {
+  return (a ? b : 0) ? a : b;
+}
@@ -0,0 +1,18 @@
+#include <stdio.h>
+
+// Only 5 meaningful lines, but 11 lines between the braces, so only
+// `--length-metric physical` flags it
+void mostly_declarations(void)
+{
+  int a;
+  int b;
+  int c;
+
+  // set up the values
+  a = 1;
+  b = 2;
+  c = 3;
+
+  printf("%d %d\n", a, b);
+  printf("%d\n", c);
+}
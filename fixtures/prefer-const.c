@@ -0,0 +1,18 @@
+#include <stdio.h>
+
+// Only numeric constants are flagged with `--prefer-const`
+#define MAX_ITEMS 10
+#define RATE (0.5f)
+#define OFFSET -1 // below the first item
+#define GREETING "hello"
+#define SQUARE(x) ((x) * (x))
+#define VERBOSE 1
+
+// Prints the scaled size
+int main(void) {
+#if VERBOSE
+  puts(GREETING);
+#endif
+  printf("%f\n", SQUARE(MAX_ITEMS + OFFSET) * RATE);
+  return 0;
+}
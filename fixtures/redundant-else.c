@@ -0,0 +1,34 @@
+#include <stdio.h>
+
+// Both branches return, so the else is redundant
+int is_positive(int x) {
+  if (x > 0) return 1; else return 2;
+}
+
+// Only some paths through the first branch return, so the else is needed
+int describe(int x) {
+  if (x > 0) {
+    if (x > 100) {
+      return 2;
+    }
+    printf("small\n");
+  } else {
+    printf("not positive\n");
+  }
+  return 0;
+}
+
+// Every path through the nested if breaks out of the loop
+void search(int limit) {
+  for (int i = 0; i < limit; i++) {
+    if (i % 2 == 0) {
+      if (i > 10) {
+        break;
+      } else {
+        continue;
+      }
+    } else {
+      printf("%d\n", i);
+    }
+  }
+}
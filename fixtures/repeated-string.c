@@ -0,0 +1,18 @@
+#include <stdio.h>
+
+#define GREETING "hello"
+
+// The same message typed twice is flagged, short strings and macros aren't
+void report(int count) {
+  if (count < 0) {
+    puts("invalid count");
+  }
+  if (count > 100) {
+    puts("invalid count");
+  }
+  printf("%d\n", count);
+  printf("%d\n", count);
+  puts(GREETING);
+  puts("ok");
+  puts("ok");
+}
@@ -0,0 +1,18 @@
+#include <stdio.h>
+
+// Variables, parameters, and functions reusing a libc name are flagged
+int read(int *index, int count) {
+  int time = 0;
+  int *link = index;
+  for (int i = 0; i < count; i++) {
+    time += link[i];
+  }
+  return time;
+}
+
+// Only declarations are flagged, not uses of the name
+int main(void) {
+  int values[3] = {1, 2, 3};
+  printf("%d\n", read(values, 3));
+  return 0;
+}
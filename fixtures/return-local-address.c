@@ -0,0 +1,21 @@
+#include <stdlib.h>
+
+// Return pointers with every kind of lifetime
+int *pointers(int param, int which) {
+  static int counter = 0;
+  int local = 1;
+  int *heap = malloc(sizeof(int));
+  if (which == 0) {
+    return &local;
+  }
+  if (which == 1) {
+    return &param;
+  }
+  if (which == 2) {
+    return &counter;
+  }
+  for (int i = 0; i < which; i++) {
+    return &i;
+  }
+  return heap;
+}
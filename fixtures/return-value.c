@@ -0,0 +1,24 @@
+#include <stdio.h>
+
+// Returns a value even though it is void
+void print_sign(int n) {
+  if (n < 0) {
+    puts("negative");
+    return 0;
+  }
+  puts("non-negative");
+  return;
+}
+
+// Returns without a value even though it returns an int
+int absolute(int n) {
+  if (n == 0) {
+    return;
+  }
+  return n < 0 ? -n : n;
+}
+
+// Pointers to void are values, so this returns one correctly
+void *first(void **items) {
+  return items[0];
+}
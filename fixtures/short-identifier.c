@@ -0,0 +1,9 @@
+#include <stdio.h>
+
+// With `--min-identifier-length 4`, `arr`, `cnt`, and `n` are flagged but `i` is allowed
+void print_all(int *arr, int cnt) {
+  for (int i = 0; i < cnt; i++) {
+    int n = arr[i];
+    printf("%d\n", n);
+  }
+}
@@ -0,0 +1,20 @@
+#include <string.h>
+
+// sizeof on each parameter gives the size of a pointer
+void clear(int *values, char names[], int count) {
+  memset(values, 0, sizeof(values));
+  memset(names, 0, sizeof names);
+  int total = sizeof(values) / sizeof(*values);
+  total = total + count + sizeof(count);
+}
+
+// arrays declared in the function really are arrays
+int sum_local(int *values) {
+  int buffer[16];
+  int size = sizeof(buffer) / sizeof(buffer[0]);
+  {
+    int values[4] = {0};
+    size = size + sizeof(values);
+  }
+  return size + sizeof(int *) + values[0];
+}
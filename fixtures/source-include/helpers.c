@@ -0,0 +1,4 @@
+// Doubles a number
+int twice(int n) {
+  return n * 2;
+}
@@ -0,0 +1,10 @@
+#include <stdio.h>
+
+// Should include a header declaring `twice` instead
+#include "helpers.c"
+
+// Prints a doubled number
+int main(void) {
+  printf("%d\n", twice(21));
+  return 0;
+}
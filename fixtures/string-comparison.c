@@ -0,0 +1,12 @@
+#include <string.h>
+
+// Comparing against a literal with == or != compares addresses
+int is_bob(const char *name) {
+  if (name == "bob") {
+    return 1;
+  }
+  if ("alice" != name) {
+    return 0;
+  }
+  return strcmp(name, "bob") == 0 && name != NULL;
+}
@@ -0,0 +1,19 @@
+#include <stdio.h>
+
+#include "util.h"
+
+// Prints a labelled number, only used in this file
+void print_result(int value) {
+  printf("Result: %d\n", value);
+}
+
+// Already static, so it is left alone
+static int offset(int value) {
+  return value + 1;
+}
+
+// Prints a scaled square
+int main(void) {
+  print_result(offset(scaled_square(3)));
+  return 0;
+}
@@ -0,0 +1,11 @@
+#include "util.h"
+
+// Doubles a number, only used in this file
+int double_it(int n) {
+  return n * 2;
+}
+
+// Squares a number and doubles the result
+int scaled_square(int n) {
+  return double_it(n * n);
+}
@@ -0,0 +1,22 @@
+#include <stdio.h>
+
+// Name a few digits, forgetting about the rest
+void name_digit(int digit) {
+  switch (digit) {
+  case 0:
+    printf("zero\n");
+    break;
+  case 1:
+    printf("one\n");
+    break;
+  }
+
+  switch (digit % 2) {
+  case 0:
+    printf("even\n");
+    break;
+  default:
+    printf("odd\n");
+    break;
+  }
+}
@@ -0,0 +1,17 @@
+#include <stdio.h>
+
+// Each ternary counts for every line it spans, flagged with 11 lines
+int ternaries(int x)
+{
+  int small = x < 10 ? 1
+                     : 0;
+  int first = 1, second = x > 0
+                              ? x
+                              : -x;
+  x > 100 ? printf("big\n")
+          : printf("small\n");
+  x = x + small;
+  x = x + first;
+  x = x + second;
+  return x;
+}
@@ -0,0 +1,13 @@
+#include <stdio.h>
+
+// Print a greeting
+// TODO: take the name as an argument
+int main(void) {
+  /*
+   * fixme: this should not be hardcoded
+   * XXX and neither should this
+   */
+  printf("Hello, world\n"); // todolist is not a marker
+
+  return 0;
+}
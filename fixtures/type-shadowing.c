@@ -0,0 +1,14 @@
+#include <stdio.h>
+
+typedef int Count;
+
+struct point {
+  int x;
+  int y;
+};
+
+// With `--warn-type-shadowing`, `Count` and `point` reuse a type's name
+void print_point(struct point point) {
+  int Count = point.x + point.y;
+  printf("%d\n", Count);
+}
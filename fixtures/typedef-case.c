@@ -0,0 +1,26 @@
+#include <stdio.h>
+
+typedef struct node {
+  int value;
+  struct node *next;
+} Node;
+
+typedef struct list_entry {
+  int key;
+} list_entry;
+
+typedef struct BinaryTree {
+  int size;
+} BinaryTree, *tree_pointer;
+
+typedef struct {
+  int x;
+  int y;
+} Point;
+
+// Prints the sizes of the types
+int main(void) {
+  printf("%zu %zu %zu %zu\n", sizeof(Node), sizeof(list_entry),
+         sizeof(BinaryTree), sizeof(Point));
+  return 0;
+}
@@ -0,0 +1,18 @@
+#include <stdio.h>
+
+#define LINE_LENGTH 80
+
+// Reads a name and a line of input and echoes them
+int main(void) {
+  char name[20];
+  char line[LINE_LENGTH];
+  char initial;
+  int age;
+  scanf("%s %d", name, &age);
+  scanf("%19s %d", name, &age);
+  scanf("%*s %c %[^\n]", &initial, line);
+  gets(line);
+  fgets(line, sizeof line, stdin);
+  printf("%s %c %d %s\n", name, initial, age, line);
+  return 0;
+}
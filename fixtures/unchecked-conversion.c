@@ -0,0 +1,11 @@
+#include <stdlib.h>
+
+// Every conversion that can't report an error is flagged
+double parse(char **argv) {
+  int count = atoi(argv[1]);
+  long total = atol(argv[2]);
+  double scale = atof(argv[3]);
+  char *end;
+  long checked = strtol(argv[4], &end, 10);
+  return count + total + scale + checked;
+}
@@ -0,0 +1,7 @@
+#include <stdio.h>
+
+// Grüße über alles, columns after these count characters rather than bytes
+void greet(int count) {
+  printf("🎉 %d\n", count); // TODO: after an emoji
+  puts("naïve"); // FIXME: über
+}
@@ -0,0 +1,27 @@
+#include <stdio.h>
+
+// Sum the numbers below a limit, forgetting to start the sum at zero
+int sum_below(int limit) {
+  int sum;
+  int i;
+  int count;
+  int size;
+  size = sizeof count;
+  for (i = 0; i < limit; i++) {
+    sum += i;
+  }
+  scanf("%d", &count);
+  printf("%d %d\n", count, size);
+  return sum;
+}
+
+// Initialized in both branches before it is read, which is fine
+int pick(int flag) {
+  int value;
+  if (flag) {
+    value = 1;
+  } else {
+    value = 2;
+  }
+  return value;
+}
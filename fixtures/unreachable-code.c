@@ -0,0 +1,27 @@
+#include <stdio.h>
+
+// Return the sign of a number
+int sign(int x) {
+  if (x < 0) {
+    return -1;
+  }
+  if (x > 0)
+    return 1;
+
+  return 0;
+  printf("This is never printed\n");
+  printf("Neither is this\n");
+}
+
+// Print the numbers below a limit, stopping at the first multiple of 7
+void count(int limit) {
+  for (int i = 0; i < limit; i++) {
+    if (i % 7 == 0) {
+      break;
+      printf("Stopped early\n");
+    }
+    printf("%d\n", i);
+    continue;
+    // only a comment here, which is fine
+  }
+}
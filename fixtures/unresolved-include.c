@@ -0,0 +1,10 @@
+#include <stdio.h>
+
+// Not found anywhere, so `--print-includes` marks it unresolved
+#include "missing.h"
+
+// Prints a greeting
+int main(void) {
+  puts("hello");
+  return 0;
+}
@@ -0,0 +1,24 @@
+#include <stddef.h>
+
+// Comparisons of unsigned values against 0 that can't change are flagged
+int count_down(unsigned int start, size_t limit) {
+  int steps = 0;
+  for (unsigned int i = start; i >= 0; i--) {
+    steps++;
+  }
+  if (limit < 0) {
+    return -1;
+  }
+  if (0 <= limit) {
+    return steps;
+  }
+  return 0;
+}
+
+// Signed values and other comparisons are left alone
+int signed_values(int value, unsigned int count) {
+  if (value >= 0 && count > 0) {
+    return 1;
+  }
+  return count == 0;
+}
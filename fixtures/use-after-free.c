@@ -0,0 +1,21 @@
+#include <stdlib.h>
+
+struct node {
+  int value;
+  struct node *next;
+};
+
+// With `--warn-uaf`, reading `head` after freeing it is flagged
+int pop(struct node *head) {
+  free(head);
+  return head->value;
+}
+
+// Assigning a freed pointer again before using it is fine
+void free_list(struct node *head) {
+  while (head != NULL) {
+    struct node *next = head->next;
+    free(head);
+    head = next;
+  }
+}
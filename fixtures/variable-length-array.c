@@ -0,0 +1,17 @@
+#include <stdio.h>
+
+#define ROWS 4
+#define cols 3
+
+// Fill a few arrays, only the ones sized at runtime are flagged
+void fill(int n) {
+  int literal[10];
+  int defined[ROWS * 2];
+  int lowercase_macro[cols];
+  int sized[sizeof(int) * 2];
+  char implicit[] = "size from initializer";
+  int runtime[n];
+  int grid[ROWS][n + 1];
+  printf("%d %s\n", literal[0] + defined[0] + lowercase_macro[0] + sized[0], implicit);
+  printf("%d %d\n", runtime[0], grid[0][0]);
+}
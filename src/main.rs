@@ -1,15 +1,787 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use ignore::gitignore::Gitignore;
+use rayon::prelude::*;
 use regex::Regex;
+use serde_json::{json, Value};
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashMap, HashSet},
     fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{self, Read},
+    iter,
     path::{Path, PathBuf},
+    process::Command,
+    sync::LazyLock,
     vec,
 };
-use tree_sitter::{Node, Query, QueryCursor, Range};
+use tree_sitter::{Node, Point, Query, QueryCursor, Range};
+
+/// The family a rule belongs to, used to group lints for reporting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Category {
+    Naming,
+    Structure,
+    Comments,
+    Correctness,
+}
+
+impl Category {
+    /// Bit set in the process exit code by `--category-exit-codes` when a lint of this category fires
+    fn exit_bit(&self) -> i32 {
+        match self {
+            Category::Naming => 1 << 0,
+            Category::Structure => 1 << 1,
+            Category::Comments => 1 << 2,
+            Category::Correctness => 1 << 3,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Category::Naming => "naming",
+            Category::Structure => "structure",
+            Category::Comments => "comments",
+            Category::Correctness => "correctness",
+        }
+    }
+}
+
+/// Ordered from most to least severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    /// Matches the SARIF `level` values
+    fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+struct RuleInfo {
+    code: &'static str,
+    category: Category,
+    severity: Severity,
+    /// Rules that are off by default are turned on by their own flag
+    enabled_by_default: bool,
+    description: &'static str,
+    /// What `--explain` prints: why the rule exists and how to fix what it reports
+    explanation: &'static str,
+    /// The message reported, with `{...}` marking the parts filled in per lint
+    message: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Rule {
+    GlobalVariable,
+    MissingFunctionComment,
+    FunctionLength,
+    MacroCase,
+    IdentifierCase,
+    TodoComment,
+    UnreachableCode,
+    FloatEquality,
+    Complexity,
+    CommentedCode,
+    BlankLines,
+    Indentation,
+    ImplicitInt,
+    ReturnLocalAddress,
+    DeadFunction,
+    MacroParentheses,
+    RedundantElse,
+    UninitializedUse,
+    VariableLengthArray,
+    SwitchDefault,
+    InfiniteLoop,
+    SizeofPointer,
+    NestedTernary,
+    StringComparison,
+    DuplicateInclude,
+    MissingPrototype,
+    EmptyStatement,
+    BitwiseCondition,
+    DeclarationAfterStatement,
+    IntegerDivision,
+    MemoryLeak,
+    MissingBraces,
+    LongStatement,
+    ReservedName,
+    UnsignedComparison,
+    UncheckedConversion,
+    ElseIfChain,
+    EmptyParameters,
+    RepeatedString,
+    PreferConst,
+    MisplacedJump,
+    HeaderDefinition,
+    AssignmentCondition,
+    ShortIdentifier,
+    TypeShadowing,
+    ArrayParameter,
+    AllocationSize,
+    BracePlacement,
+    ReturnValue,
+    UseAfterFree,
+    DeadStore,
+    SourceInclude,
+    DuplicateFunction,
+    InitializerSpacing,
+    UnboundedRead,
+    TypedefCase,
+    FileLength,
+    MainSignature,
+    SuggestStatic,
+}
+
+impl Rule {
+    const ALL: [Rule; 59] = [
+        Rule::GlobalVariable,
+        Rule::MissingFunctionComment,
+        Rule::FunctionLength,
+        Rule::MacroCase,
+        Rule::IdentifierCase,
+        Rule::TodoComment,
+        Rule::UnreachableCode,
+        Rule::FloatEquality,
+        Rule::Complexity,
+        Rule::CommentedCode,
+        Rule::BlankLines,
+        Rule::Indentation,
+        Rule::ImplicitInt,
+        Rule::ReturnLocalAddress,
+        Rule::DeadFunction,
+        Rule::MacroParentheses,
+        Rule::RedundantElse,
+        Rule::UninitializedUse,
+        Rule::VariableLengthArray,
+        Rule::SwitchDefault,
+        Rule::InfiniteLoop,
+        Rule::SizeofPointer,
+        Rule::NestedTernary,
+        Rule::StringComparison,
+        Rule::DuplicateInclude,
+        Rule::MissingPrototype,
+        Rule::EmptyStatement,
+        Rule::BitwiseCondition,
+        Rule::DeclarationAfterStatement,
+        Rule::IntegerDivision,
+        Rule::MemoryLeak,
+        Rule::MissingBraces,
+        Rule::LongStatement,
+        Rule::ReservedName,
+        Rule::UnsignedComparison,
+        Rule::UncheckedConversion,
+        Rule::ElseIfChain,
+        Rule::EmptyParameters,
+        Rule::RepeatedString,
+        Rule::PreferConst,
+        Rule::MisplacedJump,
+        Rule::HeaderDefinition,
+        Rule::AssignmentCondition,
+        Rule::ShortIdentifier,
+        Rule::TypeShadowing,
+        Rule::ArrayParameter,
+        Rule::AllocationSize,
+        Rule::BracePlacement,
+        Rule::ReturnValue,
+        Rule::UseAfterFree,
+        Rule::DeadStore,
+        Rule::SourceInclude,
+        Rule::DuplicateFunction,
+        Rule::InitializerSpacing,
+        Rule::UnboundedRead,
+        Rule::TypedefCase,
+        Rule::FileLength,
+        Rule::MainSignature,
+        Rule::SuggestStatic,
+    ];
+
+    /// The single place a rule's metadata is defined
+    fn info(&self) -> RuleInfo {
+        match self {
+            Rule::GlobalVariable => RuleInfo {
+                code: "global-variable",
+                category: Category::Structure,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "No global variables",
+                explanation: "Global variables can be read and changed by any function in the program, so following where a value comes from means reading all of it. The course asks for state to be passed through parameters and return values instead, which keeps each function's inputs and outputs visible in its signature. Move the variable into the function that owns it and pass it, or a pointer to it, to the functions that need it. If the assignment requires a global, allow it by name with `--allow-global`.",
+                message: "Global variable",
+            },
+            Rule::MissingFunctionComment => RuleInfo {
+                code: "missing-function-comment",
+                category: Category::Comments,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "Top level functions must have a comment above them",
+                explanation: "A comment above each function says what it does without making the reader work it out from the code, and the course grades for it. Write a comment directly above the function, with no blank line in between, describing what it does, its parameters, and what it returns.",
+                message: "Missing comment directly above function",
+            },
+            Rule::FunctionLength => RuleInfo {
+                code: "function-length",
+                category: Category::Structure,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "Functions may have at most 10 meaningful lines, or 10 lines between their braces with `--length-metric physical`",
+                explanation: "Short functions are easier to read, test, and get right, and the course limits each function to 10 meaningful lines to encourage breaking problems into pieces. Move a self-contained part of the function, such as a loop or a group of related statements, into a helper function with a descriptive name.",
+                message: "Function has more than 10 lines ({lines})",
+            },
+            Rule::MacroCase => RuleInfo {
+                code: "macro-case",
+                category: Category::Naming,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "Macros must be SCREAMING_SNAKE_CASE",
+                explanation: "Writing macros in SCREAMING_SNAKE_CASE marks them as macros wherever they are used, which matters because a macro is textual substitution rather than a variable or function. Rename the macro, and its uses, to use only uppercase letters, digits, and underscores.",
+                message: "Macro is not SCREAMING_SNAKE_CASE",
+            },
+            Rule::IdentifierCase => RuleInfo {
+                code: "identifier-case",
+                category: Category::Naming,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "Identifiers must all be either lower_snake_case or camelCase, or the one `--identifier-style` picks",
+                explanation: "Mixing naming styles in one program makes names harder to remember and guess. Either style is accepted as long as one is used consistently, unless `--identifier-style` picks one. Rename the identifiers in the less common style to match the rest.",
+                message: "{Snake|Camel} case identifier contributes to case inconsistency",
+            },
+            Rule::TodoComment => RuleInfo {
+                code: "todo-comment",
+                category: Category::Comments,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "No unresolved TODO markers in comments",
+                explanation: "TODO and FIXME markers note unfinished work, and submitted work should be finished. Do what the comment describes, or remove it if it no longer applies.",
+                message: "Unresolved {keyword} comment",
+            },
+            Rule::UnreachableCode => RuleInfo {
+                code: "unreachable-code",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "No unreachable code after return, break, or continue",
+                explanation: "Statements after a `return`, `break`, or `continue` in the same block can never run, which usually means the code isn't doing what was intended. Remove the unreachable statements, or move them before the jump if they were meant to run.",
+                message: "Unreachable code after {statement}",
+            },
+            Rule::FloatEquality => RuleInfo {
+                code: "float-equality",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "No equality comparison of floating-point values",
+                explanation: "Floating-point arithmetic rounds, so two calculations that should give the same value often differ in the last bits and `==` is false. Compare the absolute difference against a small tolerance instead, as in `fabs(a - b) < 1e-9`.",
+                message: "Avoid direct equality comparison of floating-point values",
+            },
+            Rule::Complexity => RuleInfo {
+                code: "complexity",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Functions may not exceed the cyclomatic complexity threshold",
+                explanation: "Cyclomatic complexity counts the independent paths through a function, and every path needs thinking through and testing. Functions with many branches are hard to follow. Split the function, or replace chains of conditions with a lookup table or a `switch`.",
+                message: "Function complexity {complexity} exceeds {max}",
+            },
+            Rule::CommentedCode => RuleInfo {
+                code: "commented-code",
+                category: Category::Comments,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "No commented-out code",
+                explanation: "Commented-out code is clutter that readers have to skip over, and version control already keeps old versions. Delete it, or turn it back into code if it is still needed.",
+                message: "Commented-out code should be removed",
+            },
+            Rule::BlankLines => RuleInfo {
+                code: "blank-lines",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "No long runs of consecutive blank lines",
+                explanation: "One blank line is enough to separate groups of statements, and longer runs spread code out so less of it fits on screen. Remove the extra blank lines.",
+                message: "Too many consecutive blank lines",
+            },
+            Rule::Indentation => RuleInfo {
+                code: "indentation",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Indentation must not mix tabs and spaces",
+                explanation: "Tabs and spaces line up differently in different editors, so indentation that mixes them looks broken to someone else. Configure the editor to insert one or the other and reindent the flagged lines to match the rest of the file.",
+                message: "Inconsistent indentation (tabs vs spaces)",
+            },
+            Rule::ImplicitInt => RuleInfo {
+                code: "implicit-int",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Functions must declare their return type",
+                explanation: "Old C assumed `int` for a function without a return type, but C99 removed that rule and it hides mistakes. Write the return type, `int` or `void`, before the function's name.",
+                message: "Function is missing an explicit return type",
+            },
+            Rule::ReturnLocalAddress => RuleInfo {
+                code: "return-local-address",
+                category: Category::Correctness,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "Functions must not return the address of a local variable",
+                explanation: "A local variable stops existing when its function returns, so a pointer to it points at memory that will be reused, and reading it later is undefined behavior. Return the value itself, allocate the memory with `malloc` and have the caller free it, or have the caller pass in a pointer to fill.",
+                message: "Returning address of local variable",
+            },
+            Rule::DeadFunction => RuleInfo {
+                code: "dead-function",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Functions must be used somewhere in the linted files",
+                explanation: "A function that is never called is code nobody runs, which usually means it was replaced or forgotten. Delete it, or call it where it was meant to be used.",
+                message: "Function '{name}' is defined but never used",
+            },
+            Rule::MacroParentheses => RuleInfo {
+                code: "macro-parentheses",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Function-like macros must parenthesize their body and arguments",
+                explanation: "Macro arguments are pasted in as text, so `#define SQUARE(x) x * x` turns `SQUARE(a + 1)` into `a + 1 * a + 1`. Wrap every use of an argument and the whole body in parentheses, as in `#define SQUARE(x) ((x) * (x))`.",
+                message: "Function-like macro body or arguments are not fully parenthesized",
+            },
+            Rule::RedundantElse => RuleInfo {
+                code: "redundant-else",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "No else after an if branch that always returns, breaks, or continues",
+                explanation: "When an `if` branch always ends in `return`, `break`, or `continue`, the code after it only runs when the condition was false, so the `else` adds nesting without changing anything. Remove the `else` and unindent its body.",
+                message: "Redundant else after early return",
+            },
+            Rule::UninitializedUse => RuleInfo {
+                code: "uninitialized-use",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Local variables must be assigned before they are read",
+                explanation: "A local variable holds whatever was in its memory until it is assigned, so reading it first gives an unpredictable value. Initialize the variable where it is declared, or assign it on every path before it is read.",
+                message: "Variable '{name}' may be used before initialization",
+            },
+            Rule::VariableLengthArray => RuleInfo {
+                code: "variable-length-array",
+                category: Category::Correctness,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "Array sizes must be compile-time constants",
+                explanation: "An array sized by a variable is allocated on the stack at run time, which can overflow the stack with no way to detect it, and the course requires fixed sizes. Use a constant or macro for the size, or allocate the array with `malloc` and free it when done.",
+                message: "Variable-length array is not allowed",
+            },
+            Rule::SwitchDefault => RuleInfo {
+                code: "switch-default",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Switch statements must have a default case",
+                explanation: "A `switch` without a `default` silently does nothing for values nobody thought of. Add a `default` case, even if it only reports an error.",
+                message: "Switch statement is missing a default case",
+            },
+            Rule::InfiniteLoop => RuleInfo {
+                code: "infinite-loop",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Loop conditions must depend on something the loop changes",
+                explanation: "A loop whose condition only uses variables the loop never changes either never runs or never stops. Update one of the condition's variables in the loop body, or fix the condition to test the variable that does change.",
+                message: "Loop condition variables are never modified in the body, possible infinite loop",
+            },
+            Rule::SizeofPointer => RuleInfo {
+                code: "sizeof-pointer",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "`sizeof` must not be used to find the length of an array parameter",
+                explanation: "An array parameter is really a pointer, so `sizeof` on it gives the size of a pointer rather than of the array, and dividing by the element size doesn't give its length. Pass the length as a separate parameter.",
+                message: "sizeof on a pointer parameter returns pointer size, not array size",
+            },
+            Rule::NestedTernary => RuleInfo {
+                code: "nested-ternary",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Ternary expressions must not be nested in another's branches",
+                explanation: "A ternary inside another ternary's branch is hard to read and easy to get wrong. Use an `if`/`else` chain, or give the inner ternary's result a name first.",
+                message: "Nested ternary expressions reduce readability",
+            },
+            Rule::StringComparison => RuleInfo {
+                code: "string-comparison",
+                category: Category::Correctness,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "Strings must be compared with `strcmp`, not `==` or `!=`",
+                explanation: "A string is a pointer to its first character, so `==` compares addresses and is false for two equal strings stored in different places. Use `strcmp(a, b) == 0` from `<string.h>`.",
+                message: "Comparing C strings with == compares pointers; use strcmp",
+            },
+            Rule::DuplicateInclude => RuleInfo {
+                code: "duplicate-include",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Each header must only be included once per file",
+                explanation: "Including the same header twice in one file does nothing the first include didn't, and without include guards it can break compilation. Remove the second `#include`.",
+                message: "Duplicate #include of '{path}'",
+            },
+            Rule::MissingPrototype => RuleInfo {
+                code: "missing-prototype",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Non-`static` functions in `.c` files must be declared in a header",
+                explanation: "A function other files can call should be declared in a header, so the compiler can check every call against its parameters. Add a prototype to the header that goes with the `.c` file, or make the function `static` if only this file uses it.",
+                message: "Function '{name}' has no prototype in a header",
+            },
+            Rule::EmptyStatement => RuleInfo {
+                code: "empty-statement",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "No statements that are just a semicolon",
+                explanation: "A stray `;`, as in `if (x);` or `while (x);`, becomes the whole body of the statement, and the intended body then runs unconditionally or only once. Remove the semicolon.",
+                message: "Empty statement (stray semicolon)",
+            },
+            Rule::BitwiseCondition => RuleInfo {
+                code: "bitwise-condition",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Conditions must use `&&` and `||` rather than `&` and `|`",
+                explanation: "`&` and `|` work on the bits of their operands and evaluate both sides, while `&&` and `||` work on truth values and stop early, so using the bitwise ones in a condition is usually a typo. Use `&&` or `||`, or compare the masked value explicitly, as in `(flags & MASK) != 0`.",
+                message: "Bitwise operator used in a boolean condition, did you mean && / ||?",
+            },
+            Rule::DeclarationAfterStatement => RuleInfo {
+                code: "declaration-after-statement",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Declarations must come before any statement in their block",
+                explanation: "C89 requires a block's declarations to come before its statements, and some course sections follow that style. Move the declaration to the top of its block.",
+                message: "Declaration after statement (not allowed in C89 style)",
+            },
+            Rule::IntegerDivision => RuleInfo {
+                code: "integer-division",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Dividing two integers truncates, even when the result is stored as a float",
+                explanation: "Dividing two integers discards the remainder before the result is converted, so `double half = 1 / 2;` is `0`. Make one operand floating point, as in `1.0 / 2` or `(double)a / b`.",
+                message: "Integer division result assigned to a floating-point type",
+            },
+            Rule::MemoryLeak => RuleInfo {
+                code: "memory-leak",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Functions that allocate memory must free it or hand it back",
+                explanation: "Memory from `malloc`, `calloc`, or `realloc` stays allocated until it is freed, so a function that loses its only pointer to it leaks it. Call `free` on the pointer before the function returns, or return the pointer so the caller can free it.",
+                message: "Allocated memory is never freed in this function",
+            },
+            Rule::MissingBraces => RuleInfo {
+                code: "missing-braces",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Bodies of `if`, `else`, and loops must be enclosed in braces",
+                explanation: "A body without braces is a single statement, so a second line indented to look like part of it runs unconditionally. Enclose every `if`, `else`, and loop body in braces.",
+                message: "Body should be enclosed in braces",
+            },
+            Rule::LongStatement => RuleInfo {
+                code: "long-statement",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "A single statement may span at most `--max-statement-lines` lines (5)",
+                explanation: "A single statement spread over many lines is hard to follow. Break it up by storing intermediate results in well-named variables.",
+                message: "Single statement spans more than {N} lines",
+            },
+            Rule::ReservedName => RuleInfo {
+                code: "reserved-name",
+                category: Category::Naming,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Variables and functions must not reuse the name of a standard library symbol",
+                explanation: "Reusing the name of a standard library function or variable shadows it, which confuses readers and can clash with the library at link time. Rename the identifier to something more specific.",
+                message: "Identifier '{name}' shadows a standard library symbol",
+            },
+            Rule::UnsignedComparison => RuleInfo {
+                code: "unsigned-comparison",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "No comparing an unsigned value against 0 in a way that can't change",
+                explanation: "An unsigned value is never negative, so `x >= 0` is always true and `x < 0` always false, which usually means a loop or check doesn't do what was meant. Use a signed type if the value can go below zero, or fix the comparison.",
+                message: "Comparison of unsigned value is always {true|false}",
+            },
+            Rule::UncheckedConversion => RuleInfo {
+                code: "unchecked-conversion",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Strings must be converted with functions that report errors",
+                explanation: "`atoi`, `atof`, and `atol` return 0 for input that isn't a number, which can't be told apart from a real 0, and overflow is undefined. Use `strtol` or `strtod`, which report where parsing stopped, and check it.",
+                message: "atoi/atof do not report conversion errors; consider strtol/strtod",
+            },
+            Rule::ElseIfChain => RuleInfo {
+                code: "else-if-chain",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Long `if`/`else if` chains comparing one variable against constants must be a `switch`",
+                explanation: "A long chain of `else if`s comparing one variable against constants is a `switch` written the long way, and harder to scan. Rewrite it as a `switch` with a `case` for each constant.",
+                message: "Consider replacing long if/else-if chain with a switch",
+            },
+            Rule::EmptyParameters => RuleInfo {
+                code: "empty-parameters",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Functions without parameters must say so with `(void)`",
+                explanation: "In C, `int f()` declares a function that takes any arguments, so the compiler can't check calls to it, while `int f(void)` takes none. Write `void` between the parentheses.",
+                message: "Empty parameter list should be (void)",
+            },
+            Rule::RepeatedString => RuleInfo {
+                code: "repeated-string",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "String literals used more than once must be a named constant",
+                explanation: "The same string typed in several places has to be changed in all of them, and a typo in one copy goes unnoticed. Define it once, as a `const char *` or a macro, and use the name.",
+                message: "Repeated string literal, consider a named constant",
+            },
+            Rule::PreferConst => RuleInfo {
+                code: "prefer-const",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Numeric constants must be `const` variables or `enum`s rather than `#define`s",
+                explanation: "A `#define` constant is text substitution with no type or scope, while a `const` variable or `enum` constant is checked by the compiler and shows up in a debugger. Replace the macro with `static const int NAME = value;` or an `enum`.",
+                message: "Prefer const or enum over #define for constants",
+            },
+            Rule::MisplacedJump => RuleInfo {
+                code: "misplaced-jump",
+                category: Category::Correctness,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "`break` may only appear in a loop or `switch`, and `continue` only in a loop",
+                explanation: "`break` only makes sense inside a loop or `switch`, and `continue` only inside a loop, and anywhere else is a compile error. Move the statement into the loop it was meant for, or use `return` to leave the function.",
+                message: "break used outside of loop or switch",
+            },
+            Rule::HeaderDefinition => RuleInfo {
+                code: "header-definition",
+                category: Category::Structure,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "Headers may only declare functions, unless they are `static` or `inline`",
+                explanation: "Every file that includes a header gets its own copy of anything defined in it, so a function defined in a header is defined more than once and linking fails. Keep only the prototype in the header and move the definition to a `.c` file, or make it `static inline` if it has to stay.",
+                message: "Function defined in a header file",
+            },
+            Rule::AssignmentCondition => RuleInfo {
+                code: "assignment-condition",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "The condition of an `if` or loop may not be an assignment, where `==` was likely meant",
+                explanation: "`if (x = 0)` assigns 0 to `x` and is always false, which is almost always a typo for `==`. Use `==`, or wrap an intended assignment in a second pair of parentheses, as in `while ((c = getchar()) != EOF)`.",
+                message: "Assignment in condition",
+            },
+            Rule::ShortIdentifier => RuleInfo {
+                code: "short-identifier",
+                category: Category::Naming,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Variables and parameters must be named with at least the minimum number of characters",
+                explanation: "Very short names like `n` or `p` make readers guess what a variable holds, and some course sections require descriptive names with `--min-identifier-length`. Rename the variable to say what it holds. The loop counters `i`, `j`, and `k` are allowed, and `--short-identifiers` changes that list.",
+                message: "Identifier '{name}' is too short ({len} chars)",
+            },
+            Rule::TypeShadowing => RuleInfo {
+                code: "type-shadowing",
+                category: Category::Naming,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Variables may not reuse the name of a `typedef` or a `struct`, `union`, or `enum` tag",
+                explanation: "A variable named the same as a type makes declarations and expressions that use the name ambiguous to readers. Rename the variable.",
+                message: "Variable '{name}' shadows a type name",
+            },
+            Rule::ArrayParameter => RuleInfo {
+                code: "array-parameter",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Parameters declared as arrays are really pointers, so they must be declared as pointers",
+                explanation: "A parameter declared as an array, as in `int values[10]`, is really a pointer, and the size is ignored, which misleads readers into thinking the array was copied or that `sizeof` gives its size. Declare the parameter as a pointer and pass the length separately.",
+                message: "Array parameter decays to a pointer; size information is lost",
+            },
+            Rule::AllocationSize => RuleInfo {
+                code: "allocation-size",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "The type in a `malloc` or `calloc` call's `sizeof` must be the type the result is stored as a pointer to",
+                explanation: "When the `sizeof` in an allocation names a different type than the pointer points to, the block is the wrong size, and a too small block is overwritten past its end. Use the pointed-to type, or better, `sizeof *p`, which always matches.",
+                message: "sizeof type in allocation does not match pointer type",
+            },
+            Rule::BracePlacement => RuleInfo {
+                code: "brace-placement",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Opening braces must all go on the same line as what they open, or all on the next line",
+                explanation: "Putting opening braces on the same line in some places and on their own line in others makes code harder to scan. Either style is fine, so move the flagged braces to match the rest of the file.",
+                message: "Inconsistent brace placement",
+            },
+            Rule::ReturnValue => RuleInfo {
+                code: "return-value",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "`void` functions must not return a value, and other functions must not return without one",
+                explanation: "A `void` function has no value to return, and a function with a return type must always return one, otherwise the caller reads garbage. Remove the value from the `return` in a `void` function, or return a value from the other.",
+                message: "void function should not return a value",
+            },
+            Rule::UseAfterFree => RuleInfo {
+                code: "use-after-free",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Pointers must not be used after being passed to `free` until they are assigned again",
+                explanation: "After `free(p)` the memory `p` points to may be reused, so reading or writing through `p` is undefined behavior that often only fails later. Stop using the pointer after freeing it, and set it to `NULL` if it is still in scope.",
+                message: "Possible use-after-free of '{name}'",
+            },
+            Rule::DeadStore => RuleInfo {
+                code: "dead-store",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Values assigned to a local variable must be read before the variable is assigned again",
+                explanation: "A value that is overwritten before it is ever read was computed for nothing, which often means a line is missing or in the wrong order. Remove the first assignment, or use the value before assigning again.",
+                message: "Value assigned to '{name}' is never used before being overwritten",
+            },
+            Rule::SourceInclude => RuleInfo {
+                code: "source-include",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "Only headers may be `#include`d, `.c` files are compiled separately",
+                explanation: "Including a `.c` file copies its definitions into the including file, so compiling both files defines everything twice and breaks separate compilation. Declare the functions in a header, include that instead, and compile and link the `.c` file separately.",
+                message: "Including a .c file is almost always a mistake",
+            },
+            Rule::DuplicateFunction => RuleInfo {
+                code: "duplicate-function",
+                category: Category::Correctness,
+                severity: Severity::Error,
+                enabled_by_default: true,
+                description: "A function may only be defined once, unless each definition is `static` in its own file",
+                explanation: "A program can only have one definition of each function, so two definitions with the same name fail to link, which in multi-file submissions usually means a function was copied into a second file. Keep one definition and call it from the other file through a prototype in a header, or make both `static` if they are meant to be separate helpers.",
+                message: "Function '{name}' is defined more than once",
+            },
+            Rule::InitializerSpacing => RuleInfo {
+                code: "initializer-spacing",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Commas in initializer lists are followed by a space and not preceded by one",
+                explanation: "Spacing initializers like `{1,2 , 3}` differently from the rest of the code makes long lists harder to scan, and some rubrics grade formatting. Put no space before each comma and one space or a line break after it, as in `{1, 2, 3}`.",
+                message: "Inconsistent spacing in initializer list",
+            },
+            Rule::UnboundedRead => RuleInfo {
+                code: "unbounded-read",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "`gets` and `scanf` string conversions without a width must not read into fixed-size arrays",
+                explanation: "`gets` and a `scanf` `%s` or `%[` conversion without a width keep writing for as long as the input goes on, so a long line overflows the array and overwrites whatever is next to it in memory. Give the conversion a width one less than the array's size, as in `%19s` for `char name[20]`, or read the line with `fgets(name, sizeof name, stdin)`.",
+                message: "Unbounded read into fixed-size buffer; {suggestion}",
+            },
+            Rule::TypedefCase => RuleInfo {
+                code: "typedef-case",
+                category: Category::Naming,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "A `typedef` of a tagged struct must name it in the same case style as the tag",
+                explanation: "`typedef struct node { ... } Node;` gives one type two names that differ only in case, so readers have to remember which spelling goes with `struct` and which doesn't. Use the same style for both, such as `typedef struct node { ... } node;` or `typedef struct Node { ... } Node;`, or leave the struct untagged if nothing refers to it by its tag.",
+                message: "Struct tag and typedef name use inconsistent casing",
+            },
+            Rule::FileLength => RuleInfo {
+                code: "file-length",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Files may have at most the given number of lines, not counting blank lines at the end",
+                explanation: "A file holding a whole program is hard to review and to navigate, and splitting it is how C programs share code between translation units. Move groups of related functions into their own `.c` file with a header declaring them.",
+                message: "File exceeds {max} lines ({lines})",
+            },
+            Rule::MainSignature => RuleInfo {
+                code: "main-signature",
+                category: Category::Correctness,
+                severity: Severity::Warning,
+                enabled_by_default: true,
+                description: "`main` must be defined as `int main(void)` or `int main(int argc, char *argv[])`",
+                explanation: "The C standard only guarantees those two forms of `main`, and its `int` return value is the program's exit status, which scripts and graders check. `void main()` leaves that status undefined. Declare `main` as `int main(void)` or `int main(int argc, char *argv[])` and `return 0;` on success.",
+                message: "Unexpected signature for main",
+            },
+            Rule::SuggestStatic => RuleInfo {
+                code: "suggest-static",
+                category: Category::Structure,
+                severity: Severity::Warning,
+                enabled_by_default: false,
+                description: "Functions only called from their own `.c` file and declared in no header should be `static`",
+                explanation: "A function that isn't `static` is visible to every file the program links, so a helper only its own file uses can clash with a function of the same name elsewhere, and readers can't tell it is private. Declare the helper `static`, or add a prototype to a header if other files are meant to call it.",
+                message: "Function '{name}' could be declared static",
+            },
+        }
+    }
+
+    /// Stable identifier for the rule in machine-readable output
+    fn code(&self) -> &'static str {
+        self.info().code
+    }
+
+    fn category(&self) -> Category {
+        self.info().category
+    }
+
+    /// Whether the rule runs with these arguments, rules that are off by default have their own flag
+    fn enabled(&self, args: &Args) -> bool {
+        if args.disable.contains(self) {
+            return false;
+        }
+        match self {
+            Rule::Complexity => args.max_complexity.is_some(),
+            Rule::FileLength => args.max_file_lines.is_some(),
+            Rule::CommentedCode => args.warn_commented_code,
+            Rule::DeadFunction => args.warn_dead_functions,
+            Rule::MissingPrototype => args.require_prototypes,
+            Rule::SuggestStatic => args.suggest_static,
+            Rule::DeclarationAfterStatement => args.declarations_first,
+            Rule::MemoryLeak => args.warn_leaks,
+            Rule::AllocationSize => args.warn_alloc_size,
+            Rule::UseAfterFree => args.warn_uaf,
+            Rule::DeadStore => args.warn_dead_stores,
+            Rule::InitializerSpacing => args.check_spacing,
+            Rule::MissingBraces => args.require_braces,
+            Rule::PreferConst => args.prefer_const,
+            Rule::ShortIdentifier => args.min_identifier_length > 0,
+            Rule::TypeShadowing => args.warn_type_shadowing,
+            Rule::TypedefCase => args.warn_typedef_case,
+            Rule::SwitchDefault => args.require_switch_default,
+            Rule::TodoComment => args.todo_keywords.iter().any(|k| !k.is_empty()),
+            Rule::ReservedName => args.reserved_names.iter().any(|n| !n.is_empty()),
+            _ => self.info().enabled_by_default,
+        }
+    }
+}
 
 #[derive(Debug)]
 struct Lint<'a> {
+    rule: Rule,
     message: String,
     text: String,
     range: Range,
@@ -18,16 +790,320 @@ struct Lint<'a> {
 }
 
 impl Lint<'_> {
-    fn print(&self) -> String {
+    /// `show_end` appends the end of the range as `-row:column`, where the column is one past the
+    /// last character, matching SARIF's `endColumn`
+    fn print(&self, show_end: bool) -> String {
+        let end = if show_end {
+            format!(
+                "-{}:{}",
+                self.range.end_point.row + 1,
+                self.range.end_point.column + 1
+            )
+        } else {
+            String::new()
+        };
         format!(
-            "{}:{}:{} {} `{}`",
+            "{}:{}:{}{} {} `{}`",
             self.file.to_str().unwrap(),
             self.range.start_point.row + 1,
             self.range.start_point.column + 1,
+            end,
             self.message,
             self.text
         )
     }
+
+    /// `path:row:col: severity: message`, the shape gcc's diagnostics have and editors know how to parse
+    fn print_gcc(&self, severity: &str) -> String {
+        format!(
+            "{}:{}:{}: {}: {}",
+            self.file.to_str().unwrap(),
+            self.range.start_point.row + 1,
+            self.range.start_point.column + 1,
+            severity,
+            self.message
+        )
+    }
+
+    /// Converts the lint's and its sublints' columns from the bytes tree-sitter counts to the
+    /// characters an editor shows, so `über` doesn't push everything after it over by one
+    fn count_columns_in_characters(&mut self, source: &str) {
+        self.range = character_columns(source, self.range);
+        for sublint in self.sublints.iter_mut().flatten() {
+            sublint.count_columns_in_characters(source);
+        }
+    }
+
+    /// `::warning file=path,line=row,col=col,...::message`, a GitHub Actions workflow command that
+    /// annotates the lint's range in the PR diff
+    fn print_github(&self) -> String {
+        // property values also end at `:` and `,`, so those are escaped as well
+        let escape = |text: &str| {
+            text.replace('%', "%25")
+                .replace('\r', "%0D")
+                .replace('\n', "%0A")
+        };
+        let property = |text: &str| escape(text).replace(':', "%3A").replace(',', "%2C");
+        format!(
+            "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}",
+            self.rule.info().severity.name(),
+            property(self.file.to_str().unwrap()),
+            self.range.start_point.row + 1,
+            self.range.start_point.column + 1,
+            self.range.end_point.row + 1,
+            self.range.end_point.column + 1,
+            property(self.rule.code()),
+            escape(&self.message)
+        )
+    }
+
+    /// The lint as a JSON object, with lines and columns counted from 1 and its sublints nested
+    fn json(&self) -> Value {
+        json!({
+            "file": self.file.to_str().unwrap(),
+            "rule": self.rule.code(),
+            "category": self.rule.category().name(),
+            "severity": self.rule.info().severity.name(),
+            "message": self.message,
+            "line": self.range.start_point.row + 1,
+            "column": self.range.start_point.column + 1,
+            "end_line": self.range.end_point.row + 1,
+            "end_column": self.range.end_point.column + 1,
+            "text": self.text,
+            "sublints": self.sublints.iter().flatten().map(Lint::json).collect::<Vec<Value>>(),
+        })
+    }
+
+    /// SARIF `physicalLocation` covering this lint's range, lines and columns are 1-based
+    fn sarif_location(&self) -> Value {
+        json!({
+            "physicalLocation": {
+                "artifactLocation": {
+                    "uri": self.file.to_str().unwrap().replace('\\', "/"),
+                },
+                "region": {
+                    "startLine": self.range.start_point.row + 1,
+                    "startColumn": self.range.start_point.column + 1,
+                    "endLine": self.range.end_point.row + 1,
+                    "endColumn": self.range.end_point.column + 1,
+                    "snippet": { "text": self.text },
+                },
+            },
+        })
+    }
+}
+
+/// Builds a SARIF 2.1.0 log with a single run containing every lint
+fn sarif(lints: &[Lint]) -> Value {
+    let rules = Rule::ALL
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.code(),
+                "shortDescription": { "text": rule.info().description },
+                "defaultConfiguration": { "level": rule.info().severity.name() },
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    let results = lints
+        .iter()
+        .map(|lint| {
+            let related_locations = lint
+                .sublints
+                .iter()
+                .flatten()
+                .enumerate()
+                .map(|(i, sublint)| {
+                    let mut location = sublint.sarif_location();
+                    location["id"] = json!(i);
+                    location["message"] = json!({ "text": sublint.message });
+                    location
+                })
+                .collect::<Vec<Value>>();
+
+            json!({
+                "ruleId": lint.rule.code(),
+                "ruleIndex": Rule::ALL.iter().position(|rule| *rule == lint.rule).unwrap(),
+                "level": lint.rule.info().severity.name(),
+                "message": { "text": lint.message },
+                "locations": [lint.sarif_location()],
+                "relatedLocations": related_locations,
+            })
+        })
+        .collect::<Vec<Value>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "cse2331-linter",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+}
+
+/// Builds a JUnit XML report with a test suite per file, where every lint is a failing test case and
+/// a file without lints gets a single passing one
+fn junit(files: &[PathBuf], lints: &[Lint]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let mut suites = String::new();
+    let mut total_tests = 0;
+    for file in files {
+        let name = xml_escape(file.to_str().unwrap());
+        let file_lints = lints
+            .iter()
+            .filter(|lint| lint.file == file)
+            .collect::<Vec<&Lint>>();
+        let tests = file_lints.len().max(1);
+        total_tests += tests;
+        suites += &format!(
+            "  <testsuite name=\"{name}\" tests=\"{tests}\" failures=\"{}\">\n",
+            file_lints.len()
+        );
+        if file_lints.is_empty() {
+            suites += &format!("    <testcase name=\"lint\" classname=\"{name}\"/>\n");
+        }
+        for lint in file_lints {
+            let mut details = lint.print(true);
+            for (i, sublint) in lint.sublints.iter().flatten().enumerate() {
+                details += &format!("\n  {}) {}", i + 1, sublint.print(true));
+            }
+            suites += &format!(
+                "    <testcase name=\"{} at {}:{}\" classname=\"{name}\">\n",
+                lint.rule.code(),
+                lint.range.start_point.row + 1,
+                lint.range.start_point.column + 1,
+            );
+            suites += &format!(
+                "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                xml_escape(&lint.message),
+                lint.rule.code(),
+                xml_escape(&details),
+            );
+            suites += "    </testcase>\n";
+        }
+        suites += "  </testsuite>\n";
+    }
+
+    xml += &format!(
+        "<testsuites name=\"cse2331-linter\" tests=\"{total_tests}\" failures=\"{}\">\n",
+        lints.len()
+    );
+    xml += &suites;
+    xml += "</testsuites>";
+    xml
+}
+
+/// Builds a Checkstyle XML report with a `<file>` per linted file and an `<error>` per lint, whose
+/// `source` is the rule code. Files without lints are still listed, as empty elements
+fn checkstyle(files: &[PathBuf], lints: &[Lint]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml += "<checkstyle version=\"4.3\">\n";
+    for file in files {
+        let name = xml_escape(file.to_str().unwrap());
+        let file_lints = lints
+            .iter()
+            .filter(|lint| lint.file == file)
+            .collect::<Vec<&Lint>>();
+        if file_lints.is_empty() {
+            xml += &format!("  <file name=\"{name}\"/>\n");
+            continue;
+        }
+        xml += &format!("  <file name=\"{name}\">\n");
+        for lint in file_lints {
+            xml += &format!(
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}\"/>\n",
+                lint.range.start_point.row + 1,
+                lint.range.start_point.column + 1,
+                lint.rule.info().severity.name(),
+                xml_escape(&lint.message),
+                lint.rule.code(),
+            );
+        }
+        xml += "  </file>\n";
+    }
+    xml += "</checkstyle>";
+    xml
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+enum ErrorFormat {
+    /// `path:row:col message` followed by the source line, with sublints numbered underneath
+    Default,
+    /// `path:row:col: error: message`, with sublints as `note:` lines
+    Gcc,
+    /// GitHub Actions `::error` and `::warning` workflow commands, which annotate the lines in a PR
+    Github,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+enum FailOn {
+    /// Only lints of rules with error severity
+    Error,
+    /// Lints of any severity
+    Warning,
+    /// Always exit 0, only reporting lints
+    Never,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+enum SortOrder {
+    /// By file, then line and column
+    Location,
+    /// By rule code, then location
+    Rule,
+    /// Errors before warnings, then location
+    Severity,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+enum LengthMetric {
+    /// The lines spanned by each meaningful statement, skipping declarations, comments, and braces
+    Statements,
+    /// Every line between the function's opening and closing braces
+    Physical,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+enum IdentifierStyle {
+    /// Either `lower_snake_case` or `camelCase`, as long as every identifier uses the same one
+    Consistent,
+    /// Only `lower_snake_case`
+    Snake,
+    /// Only `camelCase`
+    Camel,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+enum Format {
+    /// One lint per line, followed by its numbered sublints
+    Text,
+    /// JSON array of lints, each with its sublints nested inside it
+    Json,
+    /// One JSON lint per line, shaped like the objects of `json`
+    Jsonl,
+    /// SARIF 2.1.0 log for IDEs and code scanning tools
+    Sarif,
+    /// JUnit XML report for CI test dashboards
+    Junit,
+    /// Checkstyle XML report for code review tools
+    Checkstyle,
 }
 
 #[derive(Debug, PartialEq)]
@@ -44,21 +1120,363 @@ struct Identifier<'a> {
     text: String,
 }
 
-fn lint<'a>(file: &'a Path, source: &str, lints: &mut Vec<Lint<'a>>) {
+/// A variable or parameter declared in a linted file
+struct Variable<'a> {
+    file: &'a Path,
+    range: Range,
+    name: String,
+    text: String,
+}
+
+/// Meaningful lines a function body may have before it is too long
+const MAX_FUNCTION_LINES: usize = 10;
+
+/// Lines counted towards a function's length, by the kind of statement that counted them
+#[derive(Debug, Default, Clone, Copy)]
+struct LineCount {
+    expressions: usize,
+    /// `if`, loop, and `switch` headers
+    conditions: usize,
+    definitions: usize,
+    /// `break`, `continue`, and `return`
+    jumps: usize,
+}
+
+impl LineCount {
+    fn total(self) -> usize {
+        self.expressions + self.conditions + self.definitions + self.jumps
+    }
+}
+
+impl std::ops::AddAssign for LineCount {
+    fn add_assign(&mut self, other: Self) {
+        self.expressions += other.expressions;
+        self.conditions += other.conditions;
+        self.definitions += other.definitions;
+        self.jumps += other.jumps;
+    }
+}
+
+/// The lines a function counted by the kind of statement that counted them, like
+/// `6 expressions, 3 conditions`
+fn line_breakdown(count: LineCount) -> String {
+    [
+        ("expression", count.expressions),
+        ("condition", count.conditions),
+        ("definition", count.definitions),
+        ("jump", count.jumps),
+    ]
+    .into_iter()
+    .filter(|(_, count)| *count > 0)
+    .map(|(group, count)| format!("{count} {group}{}", if count != 1 { "s" } else { "" }))
+    .collect::<Vec<String>>()
+    .join(", ")
+}
+
+#[derive(Debug)]
+struct FunctionDefinition<'a> {
+    file: &'a Path,
+    range: Range,
+    name: String,
+    text: String,
+    is_static: bool,
+}
+
+/// Everything linting one file produces, the lints themselves and what the checks across files need
+struct FileResults<'a> {
+    lints: Vec<Lint<'a>>,
+    identifiers: Vec<Identifier<'a>>,
+    functions: Vec<FunctionDefinition<'a>>,
+    used_names: HashSet<String>,
+    prototypes: HashSet<String>,
+    type_names: HashSet<String>,
+    variables: Vec<Variable<'a>>,
+}
+
+impl<'a> FileResults<'a> {
+    /// Everything needed to rebuild these results without the file's source, for `--cache`
+    fn cache_json(&self) -> Value {
+        let identifiers = self
+            .identifiers
+            .iter()
+            .map(|identifier| {
+                let case = match identifier.case {
+                    IdentifierCase::LowerSnake => "snake",
+                    IdentifierCase::Camel => "camel",
+                };
+                json!({
+                    "case": case,
+                    "range": range_json(identifier.range),
+                    "text": identifier.text,
+                })
+            })
+            .collect::<Vec<Value>>();
+        let functions = self
+            .functions
+            .iter()
+            .map(|function| {
+                json!({
+                    "range": range_json(function.range),
+                    "name": function.name,
+                    "text": function.text,
+                    "is_static": function.is_static,
+                })
+            })
+            .collect::<Vec<Value>>();
+        let variables = self
+            .variables
+            .iter()
+            .map(|variable| {
+                json!({
+                    "range": range_json(variable.range),
+                    "name": variable.name,
+                    "text": variable.text,
+                })
+            })
+            .collect::<Vec<Value>>();
+        json!({
+            "lints": self.lints.iter().map(lint_cache_json).collect::<Vec<Value>>(),
+            "identifiers": identifiers,
+            "functions": functions,
+            "used_names": self.used_names,
+            "prototypes": self.prototypes,
+            "type_names": self.type_names,
+            "variables": variables,
+        })
+    }
+
+    /// The inverse of `cache_json`, `None` if the entry is from an incompatible version
+    fn from_cache_json(file: &'a Path, value: &Value) -> Option<FileResults<'a>> {
+        let string = |value: &Value| value.as_str().map(str::to_string);
+        let strings = |value: &Value| {
+            value
+                .as_array()?
+                .iter()
+                .map(string)
+                .collect::<Option<HashSet<String>>>()
+        };
+        Some(FileResults {
+            lints: value["lints"]
+                .as_array()?
+                .iter()
+                .map(|lint| lint_from_cache_json(file, lint))
+                .collect::<Option<Vec<Lint>>>()?,
+            identifiers: value["identifiers"]
+                .as_array()?
+                .iter()
+                .map(|identifier| {
+                    Some(Identifier {
+                        file,
+                        range: range_from_json(&identifier["range"])?,
+                        case: match identifier["case"].as_str()? {
+                            "snake" => IdentifierCase::LowerSnake,
+                            "camel" => IdentifierCase::Camel,
+                            _ => return None,
+                        },
+                        text: string(&identifier["text"])?,
+                    })
+                })
+                .collect::<Option<Vec<Identifier>>>()?,
+            functions: value["functions"]
+                .as_array()?
+                .iter()
+                .map(|function| {
+                    Some(FunctionDefinition {
+                        file,
+                        range: range_from_json(&function["range"])?,
+                        name: string(&function["name"])?,
+                        text: string(&function["text"])?,
+                        is_static: function["is_static"].as_bool()?,
+                    })
+                })
+                .collect::<Option<Vec<FunctionDefinition>>>()?,
+            used_names: strings(&value["used_names"])?,
+            prototypes: strings(&value["prototypes"])?,
+            type_names: strings(&value["type_names"])?,
+            variables: value["variables"]
+                .as_array()?
+                .iter()
+                .map(|variable| {
+                    Some(Variable {
+                        file,
+                        range: range_from_json(&variable["range"])?,
+                        name: string(&variable["name"])?,
+                        text: string(&variable["text"])?,
+                    })
+                })
+                .collect::<Option<Vec<Variable>>>()?,
+        })
+    }
+}
+
+fn lint_cache_json(lint: &Lint) -> Value {
+    json!({
+        "rule": lint.rule.code(),
+        "message": lint.message,
+        "text": lint.text,
+        "range": range_json(lint.range),
+        "sublints": lint.sublints.as_ref().map(|sublints| {
+            sublints.iter().map(lint_cache_json).collect::<Vec<Value>>()
+        }),
+    })
+}
+
+fn lint_from_cache_json<'a>(file: &'a Path, value: &Value) -> Option<Lint<'a>> {
+    let sublints = match &value["sublints"] {
+        Value::Null => None,
+        sublints => Some(
+            sublints
+                .as_array()?
+                .iter()
+                .map(|sublint| lint_from_cache_json(file, sublint))
+                .collect::<Option<Vec<Lint>>>()?,
+        ),
+    };
+    Some(Lint {
+        rule: parse_rule_code(value["rule"].as_str()?).ok()?,
+        message: value["message"].as_str()?.to_string(),
+        text: value["text"].as_str()?.to_string(),
+        range: range_from_json(&value["range"])?,
+        file,
+        sublints,
+    })
+}
+
+/// `[start_byte, end_byte, start_row, start_column, end_row, end_column]`
+fn range_json(range: Range) -> Value {
+    json!([
+        range.start_byte,
+        range.end_byte,
+        range.start_point.row,
+        range.start_point.column,
+        range.end_point.row,
+        range.end_point.column,
+    ])
+}
+
+fn range_from_json(value: &Value) -> Option<Range> {
+    let number = |i: usize| value.get(i)?.as_u64().map(|n| n as usize);
+    Some(Range {
+        start_byte: number(0)?,
+        end_byte: number(1)?,
+        start_point: Point::new(number(2)?, number(3)?),
+        end_point: Point::new(number(4)?, number(5)?),
+    })
+}
+
+/// Names a file's cache entry. A different linter version, configuration, path, or source gives a
+/// different key, so stale entries are never read, just left behind
+fn cache_key(file: &Path, source: &str, config: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    config.hash(&mut hasher);
+    file.hash(&mut hasher);
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A file's results from the cache, `None` if it has no usable entry
+fn read_cache<'a>(path: &Path, file: &'a Path) -> Option<FileResults<'a>> {
+    let entry = fs::read_to_string(path).ok()?;
+    FileResults::from_cache_json(file, &serde_json::from_str(&entry).ok()?)
+}
+
+/// Runs every per-file check, with columns counted in characters
+fn lint_file<'a>(file: &'a Path, source: &str, args: &Args) -> FileResults<'a> {
+    let tree = c_parser().parse(source, None).unwrap();
+    let root = tree.root_node();
+    let mut lints = lint(file, source, root, args);
+    lints.extend(lint_blank_lines(file, source, args));
+    lints.extend(lint_indentation(file, source));
+    lints.extend(lint_file_length(file, source, args));
+    lints.extend(lint_brace_placement(file, source, root));
+    if args.check_spacing {
+        lints.extend(lint_initializer_spacing(file, source, root));
+    }
+    lints.extend(lint_comments(file, source, root, args));
+    let (identifier_lints, mut identifiers) = lint_identifiers(file, source, root, args);
+    lints.extend(identifier_lints);
+    lints.extend(lint_macros(file, source, root));
+    lints.extend(lint_declarations(file, source, root));
+    lints.extend(lint_statements(file, source, root, args));
+    lints.extend(lint_expressions(file, source, root));
+    lints.extend(lint_calls(file, source, root));
+    lints.extend(lint_includes(file, source, root));
+    lints.extend(lint_repeated_strings(file, source, root, args));
+    let mut functions = vec![];
+    let mut used_names = HashSet::new();
+    let mut prototypes = HashSet::new();
+    let mut type_names = HashSet::new();
+    let mut variables = vec![];
+    if args.warn_type_shadowing {
+        collect_type_names(source, root, &mut type_names);
+        collect_variables(file, source, root, &mut variables);
+    }
+    if args.warn_dead_functions
+        || args.require_prototypes
+        || args.suggest_static
+        || Rule::DuplicateFunction.enabled(args)
+    {
+        collect_functions(file, source, root, &mut functions, &mut used_names);
+    }
+    if (args.require_prototypes || args.suggest_static)
+        && file.extension().is_some_and(|ext| ext == "h")
+    {
+        collect_prototypes(source, root, &mut prototypes);
+    }
+    lints
+        .iter_mut()
+        .for_each(|lint| lint.count_columns_in_characters(source));
+    identifiers
+        .iter_mut()
+        .for_each(|identifier| identifier.range = character_columns(source, identifier.range));
+    functions
+        .iter_mut()
+        .for_each(|function| function.range = character_columns(source, function.range));
+    variables
+        .iter_mut()
+        .for_each(|variable| variable.range = character_columns(source, variable.range));
+    FileResults {
+        lints,
+        identifiers,
+        functions,
+        used_names,
+        prototypes,
+        type_names,
+        variables,
+    }
+}
+
+/// A parser for C source. The grammar is compiled into the binary, so it only fails to load when
+/// tree-sitter and the grammar disagree on their ABI version
+fn c_parser() -> tree_sitter::Parser {
     let mut parser = tree_sitter::Parser::new();
+    if let Err(e) = parser.set_language(tree_sitter_c::language()) {
+        eprintln!("Failed to load the C grammar: {e}");
+        std::process::exit(2);
+    }
     parser
-        .set_language(tree_sitter_c::language())
-        .expect("Error loading Rust grammar");
-    let tree = parser.parse(source, None).unwrap();
-    let root_node = tree.root_node();
+}
+
+fn lint<'a>(file: &'a Path, source: &str, root_node: Node, args: &Args) -> Vec<Lint<'a>> {
+    let mut lints = vec![];
 
     let mut cursor = root_node.walk();
     for node in root_node.children(&mut cursor) {
         // top level declarations are global variables, and disallowed
         if node.kind() == "declaration" {
-            let declarator = node.child_by_field_name("declarator").unwrap();
-            if declarator.kind() == "init_declarator" || declarator.kind() == "identifier" {
+            let allowed = |identifier: Node| {
+                let name = &source[identifier.start_byte()..identifier.end_byte()];
+                args.allow_global.iter().any(|allowed| allowed == name)
+            };
+            let mut declarator_cursor = node.walk();
+            let is_global_variable = node
+                .children_by_field_name("declarator", &mut declarator_cursor)
+                .filter(|&declarator| declares_variable(declarator))
+                .any(|declarator| !allowed(declared_identifier(declarator)));
+            if is_global_variable {
                 lints.push(Lint {
+                    rule: Rule::GlobalVariable,
                     text: source
                         .lines()
                         .nth(node.range().start_point.row)
@@ -72,16 +1490,80 @@ fn lint<'a>(file: &'a Path, source: &str, lints: &mut Vec<Lint<'a>>) {
             }
         }
 
+        // old-style C falls back to an implicit `int` when the return type is left off. The grammar
+        // can't parse `main() {` as a function definition, instead it is a call missing its `;`
+        // followed by a bare block
+        if node.kind() == "expression_statement"
+            && node.child(0).unwrap().kind() == "call_expression"
+            && node.next_sibling().map(|n| n.kind()) == Some("compound_statement")
+        {
+            let declarator_range = node.child(0).unwrap().range();
+            lints.push(Lint {
+                rule: Rule::ImplicitInt,
+                text: source
+                    .lines()
+                    .nth(declarator_range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                message: "Function is missing an explicit return type".to_string(),
+                range: declarator_range,
+                file,
+                sublints: None,
+            });
+            let function = node
+                .child(0)
+                .unwrap()
+                .child_by_field_name("function")
+                .unwrap();
+            if &source[function.start_byte()..function.end_byte()] == "main" {
+                lints.push(Lint {
+                    rule: Rule::MainSignature,
+                    text: source
+                        .lines()
+                        .nth(declarator_range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    message: "Unexpected signature for main".to_string(),
+                    range: declarator_range,
+                    file,
+                    sublints: None,
+                })
+            }
+        }
+
+        if node.kind() == "function_definition" {
+            let declarator = node.child_by_field_name("declarator").unwrap();
+            let name = declared_identifier(declarator);
+            if &source[name.start_byte()..name.end_byte()] == "main"
+                && !is_standard_main(node, source)
+            {
+                let declarator_range = declarator.range();
+                lints.push(Lint {
+                    rule: Rule::MainSignature,
+                    text: source
+                        .lines()
+                        .nth(declarator_range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    message: "Unexpected signature for main".to_string(),
+                    range: declarator_range,
+                    file,
+                    sublints: None,
+                })
+            }
+        }
+
         // function declarations must have comments above them
         if node.kind() == "function_definition" {
             let prev_sibling = node
                 .prev_sibling()
                 .expect("Failed to find function declaration's previous node");
-            if !(prev_sibling.kind() == "comment"
-                && node.range().start_point.row - 1 == prev_sibling.range().end_point.row)
-            {
+            // up to `--comment-gap` blank lines may separate the comment from the function
+            let gap = node.range().start_point.row - prev_sibling.range().end_point.row;
+            if !(prev_sibling.kind() == "comment" && (1..=args.comment_gap + 1).contains(&gap)) {
                 let declarator_range = node.child_by_field_name("declarator").unwrap().range();
                 lints.push(Lint {
+                    rule: Rule::MissingFunctionComment,
                     text: source
                         .lines()
                         .nth(declarator_range.start_point.row)
@@ -96,99 +1578,2319 @@ fn lint<'a>(file: &'a Path, source: &str, lints: &mut Vec<Lint<'a>>) {
 
             let body_node = node.child_by_field_name("body").unwrap();
             let mut sublints: Vec<Lint<'a>> = vec![];
-            let linecount = count_lines_compound_statement(file, &source, body_node, &mut sublints);
-            if linecount > 10 {
+            let mut decisions: Vec<Lint<'a>> = vec![];
+            let counted = count_lines_compound_statement(
+                file,
+                source,
+                body_node,
+                &mut sublints,
+                &mut decisions,
+            );
+            // each sublint is the span of one statement (or condition), whatever the length metric
+            for sublint in &sublints {
+                let range = sublint.range;
+                if range.end_point.row - range.start_point.row + 1 > args.max_statement_lines {
+                    lints.push(Lint {
+                        rule: Rule::LongStatement,
+                        text: sublint.text.clone(),
+                        message: format!(
+                            "Single statement spans more than {} lines",
+                            args.max_statement_lines
+                        ),
+                        range,
+                        file,
+                        sublints: None,
+                    })
+                }
+            }
+
+            // only the lines of the statements themselves are counted, so the lines holding the
+            // function's opening and closing braces never count towards the limit
+            let (linecount, breakdown, sublints) = match args.length_metric {
+                LengthMetric::Statements => (counted.total(), Some(counted), Some(sublints)),
+                LengthMetric::Physical => {
+                    let range = body_node.range();
+                    let lines = (range.end_point.row - range.start_point.row).saturating_sub(1);
+                    (lines, None, None)
+                }
+            };
+            if linecount > MAX_FUNCTION_LINES {
                 let declarator_range = node.child_by_field_name("declarator").unwrap().range();
                 lints.push(Lint {
+                    rule: Rule::FunctionLength,
                     text: source
                         .lines()
                         .nth(declarator_range.start_point.row)
                         .unwrap()
                         .to_string(),
-                    message: format!("Function has more than 10 lines ({})", linecount),
+                    message: match breakdown {
+                        Some(counted) => format!(
+                            "Function has more than {MAX_FUNCTION_LINES} lines ({linecount} lines: {})",
+                            line_breakdown(counted)
+                        ),
+                        None => format!(
+                            "Function has more than {MAX_FUNCTION_LINES} lines ({linecount})"
+                        ),
+                    },
                     range: declarator_range,
                     file,
-                    sublints: Some(sublints),
+                    sublints,
                 })
             }
+
+            let addressed = address_taken(body_node, source);
+            lint_function_body(file, source, body_node, args, &addressed, &mut lints);
+
+            if args.warn_leaks {
+                lint_leak(file, source, node, &mut lints);
+            }
+
+            // every function has one path through it, each decision point adds another
+            let complexity = decisions.len() + 1;
+            if let Some(max_complexity) = args.max_complexity {
+                if complexity > max_complexity {
+                    let declarator_range = node.child_by_field_name("declarator").unwrap().range();
+                    lints.push(Lint {
+                        rule: Rule::Complexity,
+                        text: source
+                            .lines()
+                            .nth(declarator_range.start_point.row)
+                            .unwrap()
+                            .to_string(),
+                        message: format!(
+                            "Function complexity {complexity} exceeds {max_complexity}"
+                        ),
+                        range: declarator_range,
+                        file,
+                        sublints: Some(decisions),
+                    })
+                }
+            }
         }
     }
+    lints
 }
 
-fn lint_identifiers<'a>(
-    file: &'a Path,
-    source: &str,
-    lints: &mut Vec<Lint<'a>>,
-    identifiers: &mut Vec<Identifier<'a>>,
-) {
-    let query = Query::new(
-        tree_sitter_c::language(),
-        r#"
-        (declaration (identifier) @identifier)
-        (declaration (init_declarator (identifier) @identifier))
-        (parameter_list (parameter_declaration (identifier) @identifier))
-        (preproc_def) @preproc
-        (preproc_function_def) @preproc
-        "#,
-    )
-    .unwrap();
+/// Text-only scan for runs of blank lines longer than `--max-consecutive-blank-lines`
+fn lint_blank_lines<'a>(file: &'a Path, source: &str, args: &Args) -> Vec<Lint<'a>> {
+    let mut lints = vec![];
+    let mut start_byte = 0;
+    let mut blank_run = 0;
+    for (row, raw_line) in source.split_inclusive('\n').enumerate() {
+        let line = raw_line.trim_end_matches(['\n', '\r']);
+        if line.trim().is_empty() {
+            blank_run += 1;
+            // only the first excess line of a run is reported
+            if blank_run == args.max_consecutive_blank_lines + 1 {
+                lints.push(Lint {
+                    rule: Rule::BlankLines,
+                    text: line.to_string(),
+                    message: "Too many consecutive blank lines".to_string(),
+                    range: Range {
+                        start_byte,
+                        end_byte: start_byte + line.len(),
+                        start_point: Point::new(row, 0),
+                        end_point: Point::new(row, line.len()),
+                    },
+                    file,
+                    sublints: None,
+                })
+            }
+        } else {
+            blank_run = 0;
+        }
+        start_byte += raw_line.len();
+    }
+    lints
+}
 
-    let mut parser = tree_sitter::Parser::new();
-    parser
-        .set_language(tree_sitter_c::language())
-        .expect("Error loading Rust grammar");
-    let tree = parser.parse(&source, None).unwrap();
+/// Text-only count of a file's lines against `--max-file-lines`, reported on the last line that isn't
+/// blank
+fn lint_file_length<'a>(file: &'a Path, source: &str, args: &Args) -> Option<Lint<'a>> {
+    let max_lines = args.max_file_lines?;
+    let lines = source.trim_end().lines().count();
+    if lines <= max_lines {
+        return None;
+    }
+    let line = source.trim_end().lines().last().unwrap();
+    let start_byte = source.trim_end().len() - line.len();
+    Some(Lint {
+        rule: Rule::FileLength,
+        text: line.to_string(),
+        message: format!("File exceeds {max_lines} lines ({lines})"),
+        range: Range {
+            start_byte,
+            end_byte: start_byte + line.len(),
+            start_point: Point::new(lines - 1, 0),
+            end_point: Point::new(lines - 1, line.len()),
+        },
+        file,
+        sublints: None,
+    })
+}
 
-    let mut query_cursor = QueryCursor::new();
-    let all_matches = query_cursor.matches(&query, tree.root_node(), source.as_bytes());
+/// Text-only scan for indentation that mixes tabs and spaces, either within a line or against the
+/// style most of the file is indented with
+fn lint_indentation<'a>(file: &'a Path, source: &str) -> Vec<Lint<'a>> {
+    let mut lints = vec![];
+    // (row, start byte, leading whitespace, line) for every indented line
+    let mut indented = vec![];
+    let mut start_byte = 0;
+    for (row, raw_line) in source.split_inclusive('\n').enumerate() {
+        let line = raw_line.trim_end_matches(['\n', '\r']);
+        let content = line.trim_start_matches([' ', '\t']);
+        let indent = &line[..line.len() - content.len()];
+        // the ` * ` continuation lines of a block comment are aligned with a space after the tabs
+        if !indent.is_empty() && !content.is_empty() && !content.starts_with('*') {
+            indented.push((row, start_byte, indent, line));
+        }
+        start_byte += raw_line.len();
+    }
 
-    let screaming_snake_case_regex = Regex::new(r"^[A-Z0-9_]+$").unwrap();
-    let lower_snake_case_regex = Regex::new(r"^[a-z0-9_]+_[a-z0-9_]+$").unwrap();
-    let camel_case_regex = Regex::new(r"^[a-z]+(?:[A-Z][a-z0-9]*)+$").unwrap();
+    let tab_lines = indented
+        .iter()
+        .filter(|(_, _, indent, _)| !indent.contains(' '))
+        .count();
+    let space_lines = indented
+        .iter()
+        .filter(|(_, _, indent, _)| !indent.contains('\t'))
+        .count();
+    let uses_tabs = indented
+        .iter()
+        .any(|(_, _, indent, _)| indent.contains('\t'));
+    let uses_spaces = indented
+        .iter()
+        .any(|(_, _, indent, _)| indent.contains(' '));
+    if !uses_tabs || !uses_spaces {
+        return lints;
+    }
+    let predominant = if tab_lines > space_lines { '\t' } else { ' ' };
+
+    for (row, start_byte, indent, line) in indented {
+        if indent.chars().all(|c| c == predominant) {
+            continue;
+        }
+        lints.push(Lint {
+            rule: Rule::Indentation,
+            text: line.to_string(),
+            message: "Inconsistent indentation (tabs vs spaces)".to_string(),
+            range: Range {
+                start_byte,
+                end_byte: start_byte + indent.len(),
+                start_point: Point::new(row, 0),
+                end_point: Point::new(row, indent.len()),
+            },
+            file,
+            sublints: None,
+        })
+    }
+    lints
+}
+
+/// Compares where each function and statement body's `{` goes, on the line it belongs to (K&R) or
+/// on a line of its own (Allman), against the style most of the file uses
+fn lint_brace_placement<'a>(file: &'a Path, source: &str, root: Node) -> Vec<Lint<'a>> {
+    let mut blocks = vec![];
+    collect_kind(root, "compound_statement", &mut blocks);
+
+    // (brace, whether it is on a later line than what comes before it)
+    let braces = blocks
+        .into_iter()
+        .filter(|block| {
+            block.parent().is_some_and(|parent| {
+                matches!(
+                    parent.kind(),
+                    "function_definition"
+                        | "if_statement"
+                        | "else_clause"
+                        | "for_statement"
+                        | "while_statement"
+                        | "do_statement"
+                        | "switch_statement"
+                )
+            })
+        })
+        .filter_map(|block| {
+            let before = iter::successors(block.prev_sibling(), Node::prev_sibling)
+                .find(|sibling| sibling.kind() != "comment")?;
+            let brace = block.child(0)?;
+            Some((
+                brace,
+                brace.start_position().row > before.end_position().row,
+            ))
+        })
+        .collect::<Vec<(Node, bool)>>();
+
+    let allman = braces.iter().filter(|(_, own_line)| *own_line).count();
+    let predominant = allman > braces.len() - allman;
+    braces
+        .into_iter()
+        .filter(|(_, own_line)| *own_line != predominant)
+        .map(|(brace, _)| {
+            let range = brace.range();
+            Lint {
+                rule: Rule::BracePlacement,
+                text: source
+                    .lines()
+                    .nth(range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                message: "Inconsistent brace placement".to_string(),
+                range,
+                file,
+                sublints: None,
+            }
+        })
+        .collect()
+}
+
+/// Commas in `{1, 2, 3}` style initializers, which need a space or line break after them and
+/// nothing before them
+fn lint_initializer_spacing<'a>(file: &'a Path, source: &str, root: Node) -> Vec<Lint<'a>> {
+    let mut lists = vec![];
+    collect_kind(root, "initializer_list", &mut lists);
+
+    let mut lints = vec![];
+    for list in lists {
+        let mut cursor = list.walk();
+        for comma in list
+            .children(&mut cursor)
+            .filter(|child| child.kind() == ",")
+        {
+            let before = comma
+                .prev_sibling()
+                .map(|sibling| &source[sibling.end_byte()..comma.start_byte()]);
+            // a comma starting a line is a deliberate style, only a gap on the same line is off
+            let space_before = before.is_some_and(|gap| !gap.is_empty() && !gap.contains('\n'));
+            // a trailing comma can be followed directly by the closing brace
+            let space_after = comma.next_sibling().is_none_or(|sibling| {
+                sibling.kind() == "}"
+                    || source[comma.end_byte()..sibling.start_byte()]
+                        .starts_with(char::is_whitespace)
+            });
+            if space_before || !space_after {
+                let range = comma.range();
+                lints.push(Lint {
+                    rule: Rule::InitializerSpacing,
+                    text: source
+                        .lines()
+                        .nth(range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    message: "Inconsistent spacing in initializer list".to_string(),
+                    range,
+                    file,
+                    sublints: None,
+                });
+            }
+        }
+    }
+    lints
+}
+
+/// Flags commented-out code under `--warn-commented-code`, and markers like `TODO` left in comments
+fn lint_comments<'a>(file: &'a Path, source: &str, root: Node, args: &Args) -> Vec<Lint<'a>> {
+    let mut lints = vec![];
+    let todo_keywords = args
+        .todo_keywords
+        .iter()
+        .filter(|keyword| !keyword.is_empty())
+        .collect::<Vec<&String>>();
+    let todo_regex = Regex::new(&format!(
+        r"(?i)\b({})\b",
+        todo_keywords
+            .iter()
+            .map(|keyword| regex::escape(keyword))
+            .collect::<Vec<String>>()
+            .join("|")
+    ))
+    .unwrap();
+
+    let mut comments = vec![];
+    collect_kind(root, "comment", &mut comments);
+    for comment in comments {
+        let comment_range = comment.range();
+        let text = &source[comment_range.start_byte..comment_range.end_byte];
+        if args.warn_commented_code && is_commented_out_code(text) {
+            lints.push(Lint {
+                rule: Rule::CommentedCode,
+                text: source
+                    .lines()
+                    .nth(comment_range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                message: "Commented-out code should be removed".to_string(),
+                range: comment_range,
+                file,
+                sublints: None,
+            })
+        }
+
+        if todo_keywords.is_empty() {
+            continue;
+        }
+        for marker in todo_regex.find_iter(text) {
+            // the range points at the marker itself, which may be several lines into a block comment
+            let before = &text[..marker.start()];
+            let row = comment_range.start_point.row + before.matches('\n').count();
+            let column = match before.rfind('\n') {
+                Some(newline) => before.len() - newline - 1,
+                None => comment_range.start_point.column + before.len(),
+            };
+            let range = Range {
+                start_byte: comment_range.start_byte + marker.start(),
+                end_byte: comment_range.start_byte + marker.end(),
+                start_point: Point::new(row, column),
+                end_point: Point::new(row, column + marker.len()),
+            };
+            let keyword = todo_keywords
+                .iter()
+                .find(|keyword| keyword.eq_ignore_ascii_case(marker.as_str()))
+                .unwrap();
+            lints.push(Lint {
+                rule: Rule::TodoComment,
+                text: source.lines().nth(row).unwrap().to_string(),
+                message: format!("Unresolved {keyword} comment"),
+                range,
+                file,
+                sublints: None,
+            })
+        }
+    }
+    lints
+}
+
+// the casings names are checked against, shared by the passes below
+static SCREAMING_SNAKE_CASE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Z0-9_]+$").unwrap());
+static LOWER_SNAKE_CASE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-z0-9_]+_[a-z0-9_]+$").unwrap());
+static CAMEL_CASE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-z]+(?:[A-Z][a-z0-9]*)+$").unwrap());
+
+/// Checks declared names against the reserved and short name lists and typedef casing, and records
+/// every identifier's case for the file-wide style check
+fn lint_identifiers<'a>(
+    file: &'a Path,
+    source: &str,
+    root: Node,
+    args: &Args,
+) -> (Vec<Lint<'a>>, Vec<Identifier<'a>>) {
+    static QUERY: LazyLock<Query> = LazyLock::new(|| {
+        Query::new(
+            tree_sitter_c::language(),
+            r#"
+            (declaration (identifier) @identifier)
+            (declaration (init_declarator (identifier) @identifier))
+            (parameter_list (parameter_declaration (identifier) @identifier))
+            (declaration declarator: (_) @declarator)
+            (parameter_declaration declarator: (_) @declarator)
+            (function_definition declarator: (_) @declarator)
+            (type_definition) @typedef
+            "#,
+        )
+        .unwrap()
+    });
+    let mut lints = vec![];
+    let mut identifiers = vec![];
+    let declarator_index = QUERY.capture_index_for_name("declarator").unwrap();
 
-    for m in all_matches {
+    let mut query_cursor = QueryCursor::new();
+    for m in query_cursor.matches(&QUERY, root, source.as_bytes()) {
         for capture in m.captures {
-            match capture.node.kind() {
-                "preproc_def" | "preproc_function_def" => {
-                    let identifier = capture.node.child_by_field_name("name").unwrap();
+            if capture.index == declarator_index {
+                let identifier = declared_identifier(capture.node);
+                let name = &source[identifier.start_byte()..identifier.end_byte()];
+                if args.reserved_names.iter().any(|reserved| reserved == name) {
+                    let range = identifier.range();
+                    lints.push(Lint {
+                        rule: Rule::ReservedName,
+                        text: source
+                            .lines()
+                            .nth(range.start_point.row)
+                            .unwrap()
+                            .to_string(),
+                        message: format!("Identifier '{name}' shadows a standard library symbol"),
+                        range,
+                        file,
+                        sublints: None,
+                    })
+                }
+                let len = name.chars().count();
+                // unnamed parameters in prototypes, like `int *`, have no identifier to check
+                if len < args.min_identifier_length
+                    && identifier.kind() == "identifier"
+                    && declares_variable(capture.node)
+                    && !args.short_identifiers.iter().any(|short| short == name)
+                {
                     let range = identifier.range();
+                    lints.push(Lint {
+                        rule: Rule::ShortIdentifier,
+                        text: source
+                            .lines()
+                            .nth(range.start_point.row)
+                            .unwrap()
+                            .to_string(),
+                        message: format!("Identifier '{name}' is too short ({len} chars)"),
+                        range,
+                        file,
+                        sublints: None,
+                    })
+                }
+                continue;
+            }
+            match capture.node.kind() {
+                "identifier" => {
+                    let range = capture.node.range();
                     let text = &source[range.start_byte..range.end_byte];
-                    if !screaming_snake_case_regex.is_match(text) {
+                    if LOWER_SNAKE_CASE.is_match(text) {
+                        identifiers.push(Identifier {
+                            case: IdentifierCase::LowerSnake,
+                            file,
+                            range,
+                            text: text.to_string(),
+                        });
+                    } else if CAMEL_CASE.is_match(text) {
+                        identifiers.push(Identifier {
+                            case: IdentifierCase::Camel,
+                            file,
+                            range,
+                            text: text.to_string(),
+                        });
+                    }
+                }
+                "type_definition" => {
+                    let Some(tag) = capture
+                        .node
+                        .child_by_field_name("type")
+                        .filter(|ty| ty.kind() == "struct_specifier")
+                        .and_then(|ty| ty.child_by_field_name("name"))
+                    else {
+                        continue;
+                    };
+                    let cases = |name: &str| {
+                        naming_cases(name, &SCREAMING_SNAKE_CASE, &LOWER_SNAKE_CASE, &CAMEL_CASE)
+                    };
+                    let tag_cases = cases(&source[tag.start_byte()..tag.end_byte()]);
+                    let mut cursor = capture.node.walk();
+                    for alias in capture
+                        .node
+                        .children_by_field_name("declarator", &mut cursor)
+                        .filter(|declarator| declarator.kind() == "type_identifier")
+                    {
+                        let alias_cases = cases(&source[alias.start_byte()..alias.end_byte()]);
+                        if tag_cases
+                            .iter()
+                            .zip(alias_cases)
+                            .any(|(&tag, alias)| tag && alias)
+                        {
+                            continue;
+                        }
+                        let range = alias.range();
                         lints.push(Lint {
+                            rule: Rule::TypedefCase,
                             text: source
                                 .lines()
                                 .nth(range.start_point.row)
                                 .unwrap()
                                 .to_string(),
-                            message: "Macro is not SCREAMING_SNAKE_CASE".to_string(),
+                            message: "Struct tag and typedef name use inconsistent casing"
+                                .to_string(),
                             range,
                             file,
                             sublints: None,
                         })
                     }
                 }
-                "identifier" => {
-                    let range = capture.node.range();
-                    let text = &source[range.start_byte..range.end_byte];
-                    if lower_snake_case_regex.is_match(text) {
-                        identifiers.push(Identifier {
-                            case: IdentifierCase::LowerSnake,
-                            file,
+                _ => {}
+            }
+        }
+    }
+    (lints, identifiers)
+}
+
+/// Checks `#define`s: macro names, numeric constants that could be `const`, and unparenthesized
+/// function-like macros
+fn lint_macros<'a>(file: &'a Path, source: &str, root: Node) -> Vec<Lint<'a>> {
+    static QUERY: LazyLock<Query> = LazyLock::new(|| {
+        Query::new(
+            tree_sitter_c::language(),
+            r#"
+            (preproc_def) @preproc
+            (preproc_function_def) @preproc
+            "#,
+        )
+        .unwrap()
+    });
+    let mut lints = vec![];
+    // an optionally negated and parenthesized integer or floating-point literal, with any trailing
+    // comment allowed
+    let numeric_literal_regex = Regex::new(
+        r"^(\(\s*)?-?(0[xX][0-9a-fA-F]+|[0-9]+\.?[0-9]*|\.[0-9]+)([eE][+-]?[0-9]+)?[uUlLfF]*(\s*\))?\s*(//.*|/\*.*)?$",
+    )
+    .unwrap();
+    // macros tested by `#if`, `#ifdef`, and friends anywhere in the file configure the build, so
+    // they stay macros even with a numeric value
+    let word_regex = Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap();
+    let conditional_macros = Regex::new(r"(?m)^\s*#\s*(?:if|ifdef|ifndef|elif)\b(.*)$")
+        .unwrap()
+        .captures_iter(source)
+        .flat_map(|condition| word_regex.find_iter(condition.get(1).unwrap().as_str()))
+        .map(|word| word.as_str())
+        .collect::<HashSet<&str>>();
+
+    let mut query_cursor = QueryCursor::new();
+    for m in query_cursor.matches(&QUERY, root, source.as_bytes()) {
+        for capture in m.captures {
+            let identifier = capture.node.child_by_field_name("name").unwrap();
+            let range = identifier.range();
+            let text = &source[range.start_byte..range.end_byte];
+            if !SCREAMING_SNAKE_CASE.is_match(text) {
+                lints.push(Lint {
+                    rule: Rule::MacroCase,
+                    text: source
+                        .lines()
+                        .nth(range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    message: "Macro is not SCREAMING_SNAKE_CASE".to_string(),
+                    range,
+                    file,
+                    sublints: None,
+                })
+            }
+
+            let value = capture
+                .node
+                .child_by_field_name("value")
+                .map(|value| &source[value.start_byte()..value.end_byte()]);
+            if capture.node.kind() == "preproc_def"
+                && value.is_some_and(|value| numeric_literal_regex.is_match(value.trim()))
+                && !conditional_macros.contains(text)
+            {
+                lints.push(Lint {
+                    rule: Rule::PreferConst,
+                    text: source
+                        .lines()
+                        .nth(range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    message: "Prefer const or enum over #define for constants".to_string(),
+                    range,
+                    file,
+                    sublints: None,
+                })
+            }
+
+            if capture.node.kind() == "preproc_function_def"
+                && !is_macro_parenthesized(capture.node, source)
+            {
+                let value_range = capture.node.child_by_field_name("value").unwrap().range();
+                lints.push(Lint {
+                    rule: Rule::MacroParentheses,
+                    text: source
+                        .lines()
+                        .nth(range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    message: "Function-like macro body or arguments are not fully parenthesized"
+                        .to_string(),
+                    range: value_range,
+                    file,
+                    sublints: None,
+                })
+            }
+        }
+    }
+    lints
+}
+
+/// Checks declarations: array parameters, function definitions in headers, variable-length
+/// arrays, and empty parameter lists
+fn lint_declarations<'a>(file: &'a Path, source: &str, root: Node) -> Vec<Lint<'a>> {
+    static QUERY: LazyLock<Query> = LazyLock::new(|| {
+        Query::new(
+            tree_sitter_c::language(),
+            r#"
+            (preproc_def) @preproc
+            (preproc_function_def) @preproc
+            (declaration [
+              (array_declarator) @array
+              (init_declarator (array_declarator) @array)
+            ])
+            (declaration declarator: (_) @declarator)
+            (parameter_declaration declarator: (_) @declarator)
+            (function_definition declarator: (_) @declarator)
+            (function_declarator parameters: (parameter_list) @parameters)
+            "#,
+        )
+        .unwrap()
+    });
+    let mut lints = vec![];
+    // macros defined so far in this file, usable as constant array sizes
+    let mut macros: HashSet<&str> = HashSet::new();
+    let declarator_index = QUERY.capture_index_for_name("declarator").unwrap();
+
+    let mut query_cursor = QueryCursor::new();
+    for m in query_cursor.matches(&QUERY, root, source.as_bytes()) {
+        for capture in m.captures {
+            if capture.index == declarator_index {
+                let identifier = declared_identifier(capture.node);
+                // the declarator closest to the name is the outermost part of the type, so
+                // `int *rows[]` is an array while `int (*row)[3]` is a pointer
+                let is_array = iter::successors(identifier.parent(), Node::parent)
+                    .find(|declarator| declarator.kind() != "parenthesized_declarator")
+                    .is_some_and(|declarator| declarator.kind() == "array_declarator");
+                let parameter = capture.node.parent().unwrap();
+                if is_array && parameter.kind() == "parameter_declaration" {
+                    let range = parameter.range();
+                    lints.push(Lint {
+                        rule: Rule::ArrayParameter,
+                        text: source
+                            .lines()
+                            .nth(range.start_point.row)
+                            .unwrap()
+                            .to_string(),
+                        message: "Array parameter decays to a pointer; size information is lost"
+                            .to_string(),
+                        range,
+                        file,
+                        sublints: None,
+                    })
+                }
+                // every file including the header gets its own copy, which the linker rejects
+                let definition = capture.node.parent().unwrap();
+                if definition.kind() == "function_definition"
+                    && file.extension().is_some_and(|ext| ext == "h")
+                {
+                    let mut cursor = definition.walk();
+                    let is_internal = definition.children(&mut cursor).any(|child| {
+                        child.kind() == "storage_class_specifier"
+                            && matches!(
+                                &source[child.start_byte()..child.end_byte()],
+                                "static" | "inline"
+                            )
+                    });
+                    if !is_internal {
+                        let range = capture.node.range();
+                        lints.push(Lint {
+                            rule: Rule::HeaderDefinition,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message: "Function defined in a header file".to_string(),
                             range,
-                            text: text.to_string(),
+                            file,
+                            sublints: None,
+                        })
+                    }
+                }
+                continue;
+            }
+            match capture.node.kind() {
+                "preproc_def" | "preproc_function_def" => {
+                    let name = capture.node.child_by_field_name("name").unwrap();
+                    macros.insert(&source[name.start_byte()..name.end_byte()]);
+                }
+                "array_declarator" => {
+                    // `int grid[ROWS][n]` nests one array declarator in another, so check them all
+                    let mut declarator = Some(capture.node);
+                    while let Some(array) = declarator.filter(|d| d.kind() == "array_declarator") {
+                        let is_constant = array.child_by_field_name("size").is_none_or(|size| {
+                            is_constant_expression(size, source, &macros, &SCREAMING_SNAKE_CASE)
                         });
-                    } else if camel_case_regex.is_match(text) {
-                        identifiers.push(Identifier {
-                            case: IdentifierCase::Camel,
+                        if !is_constant {
+                            let range = capture.node.range();
+                            lints.push(Lint {
+                                rule: Rule::VariableLengthArray,
+                                text: source
+                                    .lines()
+                                    .nth(range.start_point.row)
+                                    .unwrap()
+                                    .to_string(),
+                                message: "Variable-length array is not allowed".to_string(),
+                                range,
+                                file,
+                                sublints: None,
+                            });
+                            break;
+                        }
+                        declarator = array.child_by_field_name("declarator");
+                    }
+                }
+                // `f()` takes any arguments in C, only `f(void)` takes none
+                "parameter_list" if capture.node.named_child_count() == 0 => {
+                    let range = capture.node.range();
+                    lints.push(Lint {
+                        rule: Rule::EmptyParameters,
+                        text: source
+                            .lines()
+                            .nth(range.start_point.row)
+                            .unwrap()
+                            .to_string(),
+                        message: "Empty parameter list should be (void)".to_string(),
+                        range,
+                        file,
+                        sublints: None,
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+    lints
+}
+
+/// Checks the shape of statements: declarations after statements, long else-if chains, missing
+/// braces and `default` cases, stray semicolons, and misplaced `break`/`continue`
+fn lint_statements<'a>(file: &'a Path, source: &str, root: Node, args: &Args) -> Vec<Lint<'a>> {
+    static QUERY: LazyLock<Query> = LazyLock::new(|| {
+        Query::new(
+            tree_sitter_c::language(),
+            r#"
+            (compound_statement) @block
+            [
+              (if_statement)
+              (else_clause)
+              (for_statement)
+              (while_statement)
+              (do_statement)
+            ] @braced
+            (switch_statement) @switch
+            (expression_statement) @statement
+            (break_statement) @jump
+            (continue_statement) @jump
+            "#,
+        )
+        .unwrap()
+    });
+    let mut lints = vec![];
+
+    let mut query_cursor = QueryCursor::new();
+    for m in query_cursor.matches(&QUERY, root, source.as_bytes()) {
+        for capture in m.captures {
+            match capture.node.kind() {
+                "compound_statement" if args.declarations_first => {
+                    let mut cursor = capture.node.walk();
+                    let mut seen_statement = false;
+                    for child in capture.node.named_children(&mut cursor) {
+                        match child.kind() {
+                            "declaration" if seen_statement => {
+                                let range = child.range();
+                                lints.push(Lint {
+                                    rule: Rule::DeclarationAfterStatement,
+                                    text: source
+                                        .lines()
+                                        .nth(range.start_point.row)
+                                        .unwrap()
+                                        .to_string(),
+                                    message:
+                                        "Declaration after statement (not allowed in C89 style)"
+                                            .to_string(),
+                                    range,
+                                    file,
+                                    sublints: None,
+                                })
+                            }
+                            "declaration" | "comment" => {}
+                            _ => seen_statement = true,
+                        }
+                    }
+                }
+                "if_statement" | "else_clause" | "for_statement" | "while_statement"
+                | "do_statement" => {
+                    // only the head of a chain is checked, not each `else if` within it
+                    let is_chain_head = capture.node.kind() == "if_statement"
+                        && capture.node.parent().unwrap().kind() != "else_clause";
+                    if is_chain_head && switch_like_branches(capture.node, source) > args.max_elseif
+                    {
+                        let condition = capture.node.child_by_field_name("condition").unwrap();
+                        let range = Range {
+                            start_byte: capture.node.start_byte(),
+                            end_byte: condition.end_byte(),
+                            start_point: capture.node.start_position(),
+                            end_point: condition.end_position(),
+                        };
+                        lints.push(Lint {
+                            rule: Rule::ElseIfChain,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message: "Consider replacing long if/else-if chain with a switch"
+                                .to_string(),
+                            range,
                             file,
+                            sublints: None,
+                        })
+                    }
+
+                    if !args.require_braces {
+                        continue;
+                    }
+                    let body = match capture.node.kind() {
+                        "if_statement" => capture.node.child_by_field_name("consequence"),
+                        // `else if` chains are fine, the `if` is checked on its own
+                        "else_clause" => capture
+                            .node
+                            .named_child(0)
+                            .filter(|body| body.kind() != "if_statement"),
+                        _ => capture.node.child_by_field_name("body"),
+                    };
+                    if let Some(body) = body.filter(|body| body.kind() != "compound_statement") {
+                        let range = body.range();
+                        lints.push(Lint {
+                            rule: Rule::MissingBraces,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message: "Body should be enclosed in braces".to_string(),
                             range,
-                            text: text.to_string(),
-                        });
+                            file,
+                            sublints: None,
+                        })
+                    }
+                }
+                "switch_statement" if args.require_switch_default => {
+                    let body = capture.node.child_by_field_name("body").unwrap();
+                    let mut cursor = body.walk();
+                    let has_default = body.named_children(&mut cursor).any(|case| {
+                        case.kind() == "case_statement"
+                            && case.child_by_field_name("value").is_none()
+                    });
+                    if !has_default {
+                        let range = capture.node.range();
+                        lints.push(Lint {
+                            rule: Rule::SwitchDefault,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message: "Switch statement is missing a default case".to_string(),
+                            range,
+                            file,
+                            sublints: None,
+                        })
+                    }
+                }
+                // a `for (;;)` header's semicolons are part of the loop, not statements
+                "expression_statement" if capture.node.named_child_count() == 0 => {
+                    let range = capture.node.range();
+                    lints.push(Lint {
+                        rule: Rule::EmptyStatement,
+                        text: source
+                            .lines()
+                            .nth(range.start_point.row)
+                            .unwrap()
+                            .to_string(),
+                        message: "Empty statement (stray semicolon)".to_string(),
+                        range,
+                        file,
+                        sublints: None,
+                    })
+                }
+                "break_statement" | "continue_statement" => {
+                    let is_break = capture.node.kind() == "break_statement";
+                    // the nearest enclosing construct the jump could apply to, stopping at the
+                    // function so a jump can't escape into whatever contains it
+                    let target = iter::successors(capture.node.parent(), Node::parent).find(|n| {
+                        matches!(
+                            n.kind(),
+                            "for_statement"
+                                | "while_statement"
+                                | "do_statement"
+                                | "function_definition"
+                        ) || (is_break && n.kind() == "switch_statement")
+                    });
+                    if target.is_none_or(|target| target.kind() == "function_definition") {
+                        let range = capture.node.range();
+                        lints.push(Lint {
+                            rule: Rule::MisplacedJump,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message: if is_break {
+                                "break used outside of loop or switch"
+                            } else {
+                                "continue used outside of loop"
+                            }
+                            .to_string(),
+                            range,
+                            file,
+                            sublints: None,
+                        })
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    lints
+}
+
+/// Checks expressions: assignments and bitwise operators in conditions, nested ternaries, `sizeof` on
+/// pointer parameters, and comparisons and divisions that depend on the operands' declared types
+fn lint_expressions<'a>(file: &'a Path, source: &str, root: Node) -> Vec<Lint<'a>> {
+    static QUERY: LazyLock<Query> = LazyLock::new(|| {
+        Query::new(
+            tree_sitter_c::language(),
+            r#"
+            (declaration (identifier) @identifier)
+            (declaration (init_declarator (identifier) @identifier))
+            (parameter_list (parameter_declaration (identifier) @identifier))
+            (assignment_expression) @assignment
+            (conditional_expression) @conditional
+            (sizeof_expression) @sizeof
+            (binary_expression) @binary
+            "#,
+        )
+        .unwrap()
+    });
+    let mut lints = vec![];
+    // names declared `float`/`double` so far in this file, scope is not tracked
+    let mut float_identifiers: HashSet<&str> = HashSet::new();
+    // names declared as an integer type so far in this file, scope is not tracked either
+    let mut integer_identifiers: HashSet<&str> = HashSet::new();
+    // names declared `unsigned` so far, and names declared with any other type
+    let mut unsigned_identifiers: HashSet<&str> = HashSet::new();
+    let mut signed_identifiers: HashSet<&str> = HashSet::new();
+
+    let mut query_cursor = QueryCursor::new();
+    for m in query_cursor.matches(&QUERY, root, source.as_bytes()) {
+        for capture in m.captures {
+            match capture.node.kind() {
+                "identifier" => {
+                    let range = capture.node.range();
+                    let text = &source[range.start_byte..range.end_byte];
+                    if is_float_declaration(capture.node, source) {
+                        float_identifiers.insert(text);
+                    }
+                    if is_integer_declaration(capture.node, source) {
+                        integer_identifiers.insert(text);
+                    }
+                    if is_unsigned_declaration(capture.node, source) {
+                        unsigned_identifiers.insert(text);
+                    } else {
+                        signed_identifiers.insert(text);
+                    }
+                }
+                // an extra pair of parentheses, `if ((c = next()))`, says the assignment is intended
+                "assignment_expression"
+                    if is_condition(capture.node)
+                        && capture.node.child_by_field_name("operator").unwrap().kind() == "=" =>
+                {
+                    let range = capture.node.range();
+                    lints.push(Lint {
+                        rule: Rule::AssignmentCondition,
+                        text: source
+                            .lines()
+                            .nth(range.start_point.row)
+                            .unwrap()
+                            .to_string(),
+                        message: "Assignment in condition".to_string(),
+                        range,
+                        file,
+                        sublints: None,
+                    })
+                }
+                "conditional_expression" => {
+                    for field in ["consequence", "alternative"] {
+                        let mut branch = capture.node.child_by_field_name(field);
+                        while let Some(node) =
+                            branch.filter(|node| node.kind() == "parenthesized_expression")
+                        {
+                            branch = node.named_child(0);
+                        }
+                        let Some(inner) =
+                            branch.filter(|node| node.kind() == "conditional_expression")
+                        else {
+                            continue;
+                        };
+                        let range = inner.range();
+                        lints.push(Lint {
+                            rule: Rule::NestedTernary,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message: "Nested ternary expressions reduce readability".to_string(),
+                            range,
+                            file,
+                            sublints: None,
+                        })
+                    }
+                }
+                "sizeof_expression" => {
+                    // `sizeof(p) + 1` parses as `sizeof((p) + 1)`, the real operand is leftmost
+                    let mut operand = capture.node.child_by_field_name("value");
+                    while let Some(node) = operand.filter(|node| node.kind() == "binary_expression")
+                    {
+                        operand = node.child_by_field_name("left");
+                    }
+                    if let Some(node) =
+                        operand.filter(|node| node.kind() == "parenthesized_expression")
+                    {
+                        operand = node.named_child(0);
+                    }
+                    // `sizeof(p)` alone can't be told apart from `sizeof(type)` by the parser
+                    if let Some(descriptor) = capture.node.child_by_field_name("type") {
+                        operand = descriptor
+                            .child_by_field_name("type")
+                            .filter(|_| descriptor.named_child_count() == 1);
+                    }
+                    let Some(operand) = operand
+                        .filter(|node| matches!(node.kind(), "identifier" | "type_identifier"))
+                    else {
+                        continue;
+                    };
+                    let name = &source[operand.start_byte()..operand.end_byte()];
+                    // array parameters are pointers too, locals that are arrays are left alone
+                    let is_pointer_parameter = local_declaration(operand, name, source)
+                        .filter(|declaration| declaration.kind() == "parameter_declaration")
+                        .and_then(|parameter| parameter.child_by_field_name("declarator"))
+                        .is_some_and(|declarator| {
+                            matches!(declarator.kind(), "pointer_declarator" | "array_declarator")
+                        });
+                    if is_pointer_parameter {
+                        let range = capture.node.range();
+                        lints.push(Lint {
+                            rule: Rule::SizeofPointer,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message:
+                                "sizeof on a pointer parameter returns pointer size, not array size"
+                                    .to_string(),
+                            range,
+                            file,
+                            sublints: None,
+                        })
+                    }
+                }
+                "binary_expression" => {
+                    let node = capture.node;
+                    let operator = node.child_by_field_name("operator").unwrap();
+                    let left = node.child_by_field_name("left").unwrap();
+                    let right = node.child_by_field_name("right").unwrap();
+                    // testing against a mask like `flags & 1` or `flags & FLAG_SET` is deliberate
+                    let is_mask = |operand: Node| {
+                        let text = &source[operand.start_byte()..operand.end_byte()];
+                        operand.kind() == "number_literal"
+                            || (operand.kind() == "identifier"
+                                && SCREAMING_SNAKE_CASE.is_match(text))
+                    };
+                    if matches!(operator.kind(), "&" | "|")
+                        && is_condition(node)
+                        && !is_mask(left)
+                        && !is_mask(right)
+                    {
+                        let range = operator.range();
+                        lints.push(Lint {
+                            rule: Rule::BitwiseCondition,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message:
+                                "Bitwise operator used in a boolean condition, did you mean && / ||?"
+                                    .to_string(),
+                            range,
+                            file,
+                            sublints: None,
+                        })
+                    }
+                    // a name declared both ways somewhere in the file isn't known to be an integer
+                    let is_integer = |operand: Node| {
+                        let text = &source[operand.start_byte()..operand.end_byte()];
+                        match operand.kind() {
+                            "number_literal" => {
+                                let is_hex = text.starts_with("0x") || text.starts_with("0X");
+                                !text.contains('.') && (is_hex || !text.contains(['e', 'E']))
+                            }
+                            "char_literal" => true,
+                            "identifier" => {
+                                integer_identifiers.contains(text)
+                                    && !float_identifiers.contains(text)
+                            }
+                            _ => false,
+                        }
+                    };
+                    if operator.kind() == "/"
+                        && is_integer(left)
+                        && is_integer(right)
+                        && is_stored_as_float(node, source, &float_identifiers)
+                    {
+                        let range = node.range();
+                        lints.push(Lint {
+                            rule: Rule::IntegerDivision,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message: "Integer division result assigned to a floating-point type"
+                                .to_string(),
+                            range,
+                            file,
+                            sublints: None,
+                        })
+                    }
+                    let is_unsigned = |operand: Node| {
+                        let text = &source[operand.start_byte()..operand.end_byte()];
+                        operand.kind() == "identifier"
+                            && unsigned_identifiers.contains(text)
+                            && !signed_identifiers.contains(text)
+                    };
+                    let is_zero = |operand: Node| {
+                        operand.kind() == "number_literal"
+                            && matches!(
+                                &source[operand.start_byte()..operand.end_byte()],
+                                "0" | "0u" | "0U"
+                            )
+                    };
+                    // `u >= 0` and `0 <= u` always hold, `u < 0` and `0 > u` never do
+                    let always = match operator.kind() {
+                        ">=" if is_unsigned(left) && is_zero(right) => Some(true),
+                        "<=" if is_zero(left) && is_unsigned(right) => Some(true),
+                        "<" if is_unsigned(left) && is_zero(right) => Some(false),
+                        ">" if is_zero(left) && is_unsigned(right) => Some(false),
+                        _ => None,
+                    };
+                    if let Some(always) = always {
+                        let range = node.range();
+                        lints.push(Lint {
+                            rule: Rule::UnsignedComparison,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message: format!("Comparison of unsigned value is always {always}"),
+                            range,
+                            file,
+                            sublints: None,
+                        })
+                    }
+                    if !matches!(operator.kind(), "==" | "!=") {
+                        continue;
+                    }
+                    let is_float = |operand: Node| {
+                        let text = &source[operand.start_byte()..operand.end_byte()];
+                        match operand.kind() {
+                            "number_literal" => text.contains('.'),
+                            "identifier" => float_identifiers.contains(text),
+                            _ => false,
+                        }
+                    };
+                    if is_float(left) || is_float(right) {
+                        let range = node.range();
+                        lints.push(Lint {
+                            rule: Rule::FloatEquality,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message: "Avoid direct equality comparison of floating-point values"
+                                .to_string(),
+                            range,
+                            file,
+                            sublints: None,
+                        })
+                    }
+                    let is_string = |operand: Node| {
+                        matches!(operand.kind(), "string_literal" | "concatenated_string")
+                    };
+                    if is_string(left) || is_string(right) {
+                        let range = operator.range();
+                        lints.push(Lint {
+                            rule: Rule::StringComparison,
+                            text: source
+                                .lines()
+                                .nth(range.start_point.row)
+                                .unwrap()
+                                .to_string(),
+                            message: "Comparing C strings with == compares pointers; use strcmp"
+                                .to_string(),
+                            range,
+                            file,
+                            sublints: None,
+                        })
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    lints
+}
+
+/// Checks calls to `atoi` and friends, unbounded reads, and allocation sizes
+fn lint_calls<'a>(file: &'a Path, source: &str, root: Node) -> Vec<Lint<'a>> {
+    let mut lints = vec![];
+    let mut calls = vec![];
+    collect_kind(root, "call_expression", &mut calls);
+    for call in calls {
+        let function = call.child_by_field_name("function").unwrap();
+        let name = &source[function.start_byte()..function.end_byte()];
+        if matches!(name, "atoi" | "atof" | "atol" | "atoll") {
+            let range = call.range();
+            lints.push(Lint {
+                rule: Rule::UncheckedConversion,
+                text: source
+                    .lines()
+                    .nth(range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                message: "atoi/atof do not report conversion errors; consider strtol/strtod"
+                    .to_string(),
+                range,
+                file,
+                sublints: None,
+            })
+        }
+        if let Some(suggestion) = unbounded_read_fix(call, name, source) {
+            let range = call.range();
+            lints.push(Lint {
+                rule: Rule::UnboundedRead,
+                text: source
+                    .lines()
+                    .nth(range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                message: format!("Unbounded read into fixed-size buffer; {suggestion}"),
+                range,
+                file,
+                sublints: None,
+            })
+        }
+        if matches!(name, "malloc" | "calloc") {
+            let mut sizeofs = vec![];
+            collect_kind(call, "sizeof_expression", &mut sizeofs);
+            // `sizeof *p` and `sizeof(p[0])` always match, so only types are checked
+            let types = sizeofs
+                .iter()
+                .filter_map(|sizeof| sizeof.child_by_field_name("type"))
+                .collect::<Vec<Node>>();
+            let normalized = |node: Node| {
+                source[node.start_byte()..node.end_byte()].replace(char::is_whitespace, "")
+            };
+            if let Some(pointee) = allocated_type(call, source) {
+                if !types.is_empty() && types.iter().all(|&ty| normalized(ty) != pointee) {
+                    let range = types[0].range();
+                    lints.push(Lint {
+                        rule: Rule::AllocationSize,
+                        text: source
+                            .lines()
+                            .nth(range.start_point.row)
+                            .unwrap()
+                            .to_string(),
+                        message: "sizeof type in allocation does not match pointer type"
+                            .to_string(),
+                        range,
+                        file,
+                        sublints: None,
+                    })
+                }
+            }
+        }
+    }
+    lints
+}
+
+/// Checks `#include`s of `.c` files and repeated `#include`s
+fn lint_includes<'a>(file: &'a Path, source: &str, root: Node) -> Vec<Lint<'a>> {
+    let mut lints = vec![];
+    // paths `#include`d so far in this file, spelled as written
+    let mut includes: HashSet<&str> = HashSet::new();
+
+    let mut directives = vec![];
+    collect_kind(root, "preproc_include", &mut directives);
+    for include in directives {
+        let path = include.child_by_field_name("path").unwrap();
+        let text = &source[path.start_byte()..path.end_byte()];
+        // the directive's own range runs on to the start of the next line
+        let range = Range {
+            start_byte: include.start_byte(),
+            end_byte: path.end_byte(),
+            start_point: include.start_position(),
+            end_point: path.end_position(),
+        };
+        // the file is still followed, so its definitions are linted like any other
+        if path.kind() == "string_literal" && text.ends_with(".c\"") {
+            lints.push(Lint {
+                rule: Rule::SourceInclude,
+                text: source
+                    .lines()
+                    .nth(range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                message: "Including a .c file is almost always a mistake".to_string(),
+                range,
+                file,
+                sublints: None,
+            })
+        }
+        if !includes.insert(text) {
+            lints.push(Lint {
+                rule: Rule::DuplicateInclude,
+                text: source
+                    .lines()
+                    .nth(range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                message: format!("Duplicate #include of '{}'", &text[1..text.len() - 1]),
+                range,
+                file,
+                sublints: None,
+            })
+        }
+    }
+    lints
+}
+
+/// Flags string literals spelled the same more than once in a file
+fn lint_repeated_strings<'a>(
+    file: &'a Path,
+    source: &str,
+    root: Node,
+    args: &Args,
+) -> Vec<Lint<'a>> {
+    let mut lints = vec![];
+    // every occurrence of each string literal in the file, keyed by its spelling with the quotes
+    let mut strings: HashMap<&str, Vec<Range>> = HashMap::new();
+
+    let mut literals = vec![];
+    collect_kind(root, "string_literal", &mut literals);
+    for string in literals {
+        let text = &source[string.start_byte()..string.end_byte()];
+        let in_preprocessor = iter::successors(string.parent(), Node::parent).any(|ancestor| {
+            matches!(
+                ancestor.kind(),
+                "preproc_def" | "preproc_function_def" | "preproc_include"
+            )
+        });
+        // the quotes take two characters
+        let is_format = args.allow_repeated_format_strings && text.contains('%');
+        if text.chars().count() > 4 && !in_preprocessor && !is_format {
+            strings.entry(text).or_default().push(string.range());
+        }
+    }
+    for range in strings
+        .into_values()
+        .filter(|ranges| ranges.len() > 1)
+        .flatten()
+    {
+        lints.push(Lint {
+            rule: Rule::RepeatedString,
+            text: source
+                .lines()
+                .nth(range.start_point.row)
+                .unwrap()
+                .to_string(),
+            message: "Repeated string literal, consider a named constant".to_string(),
+            range,
+            file,
+            sublints: None,
+        })
+    }
+    lints
+}
+
+/// Records the functions defined in a file and every name it mentions outside of a function's own
+/// declarator. Any mention counts as a use, so functions only passed around as pointers aren't dead
+fn collect_functions<'a>(
+    file: &'a Path,
+    source: &str,
+    root: Node,
+    definitions: &mut Vec<FunctionDefinition<'a>>,
+    used: &mut HashSet<String>,
+) {
+    static QUERY: LazyLock<Query> = LazyLock::new(|| {
+        Query::new(
+            tree_sitter_c::language(),
+            r#"
+            (function_definition declarator: (function_declarator declarator: (identifier) @definition))
+            (function_definition
+              declarator: (pointer_declarator
+                declarator: (function_declarator declarator: (identifier) @definition)))
+            (identifier) @identifier
+            "#,
+        )
+        .unwrap()
+    });
+    let query = &*QUERY;
+
+    let mut query_cursor = QueryCursor::new();
+    let definition_index = query.capture_index_for_name("definition").unwrap();
+    for m in query_cursor.matches(query, root, source.as_bytes()) {
+        for capture in m.captures {
+            let range = capture.node.range();
+            let name = &source[range.start_byte..range.end_byte];
+            if capture.index == definition_index {
+                let mut definition = capture.node;
+                while definition.kind() != "function_definition" {
+                    definition = definition.parent().unwrap();
+                }
+                let mut cursor = definition.walk();
+                let is_static = definition.children(&mut cursor).any(|child| {
+                    child.kind() == "storage_class_specifier"
+                        && &source[child.start_byte()..child.end_byte()] == "static"
+                });
+                definitions.push(FunctionDefinition {
+                    file,
+                    range,
+                    name: name.to_string(),
+                    text: source
+                        .lines()
+                        .nth(range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    is_static,
+                });
+            } else if capture.node.parent().unwrap().kind() != "function_declarator" {
+                used.insert(name.to_string());
+            }
+        }
+    }
+}
+
+/// How many conditions in a row of an `if`/`else if` chain compare the same variable against a
+/// constant with `==`, like the cases of a `switch` would
+fn switch_like_branches(if_statement: Node, source: &str) -> usize {
+    let compared = |statement: Node| {
+        let condition = statement.child_by_field_name("condition")?.named_child(0)?;
+        if condition.kind() != "binary_expression"
+            || condition.child_by_field_name("operator")?.kind() != "=="
+        {
+            return None;
+        }
+        let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+        let is_constant = |node: Node| match node.kind() {
+            "number_literal" | "char_literal" => true,
+            "identifier" => text(node)
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'),
+            _ => false,
+        };
+        let left = condition.child_by_field_name("left")?;
+        let right = condition.child_by_field_name("right")?;
+        if left.kind() == "identifier" && is_constant(right) && !is_constant(left) {
+            Some(text(left))
+        } else if right.kind() == "identifier" && is_constant(left) && !is_constant(right) {
+            Some(text(right))
+        } else {
+            None
+        }
+    };
+
+    let Some(name) = compared(if_statement) else {
+        return 0;
+    };
+    let mut branches = 1;
+    let mut statement = if_statement;
+    while let Some(next) = statement
+        .child_by_field_name("alternative")
+        .and_then(|clause| clause.named_child(0))
+        .filter(|next| next.kind() == "if_statement")
+    {
+        if compared(next) != Some(name) {
+            break;
+        }
+        branches += 1;
+        statement = next;
+    }
+    branches
+}
+
+/// Whether the function a statement is in returns `void`, `None` outside of a function. `void *f()`
+/// returns a pointer, so only a plain `void` with the function's declarator directly under it counts
+fn returns_void(statement: Node, source: &str) -> Option<bool> {
+    let function = iter::successors(statement.parent(), Node::parent)
+        .find(|ancestor| ancestor.kind() == "function_definition")?;
+    let ty = function.child_by_field_name("type")?;
+    let declarator = function.child_by_field_name("declarator")?;
+    Some(
+        &source[ty.start_byte()..ty.end_byte()] == "void"
+            && declarator.kind() == "function_declarator",
+    )
+}
+
+/// Whether an expression is the whole condition of an `if`, `while`, `do`/`while`, or `for`
+fn is_condition(node: Node) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    let (statement, condition) = match parent.kind() {
+        "parenthesized_expression" => (parent.parent(), parent),
+        "for_statement" => (Some(parent), node),
+        _ => return false,
+    };
+    statement.is_some_and(|statement| {
+        matches!(
+            statement.kind(),
+            "if_statement" | "while_statement" | "do_statement" | "for_statement"
+        ) && statement.child_by_field_name("condition") == Some(condition)
+    })
+}
+
+/// Records the name of every function a header declares a prototype for
+fn collect_prototypes(source: &str, root: Node, prototypes: &mut HashSet<String>) {
+    static QUERY: LazyLock<Query> = LazyLock::new(|| {
+        Query::new(
+            tree_sitter_c::language(),
+            r#"
+            (declaration declarator: (function_declarator declarator: (identifier) @prototype))
+            (declaration
+              declarator: (pointer_declarator
+                declarator: (function_declarator declarator: (identifier) @prototype)))
+            "#,
+        )
+        .unwrap()
+    });
+    let query = &*QUERY;
+
+    let mut query_cursor = QueryCursor::new();
+    for m in query_cursor.matches(query, root, source.as_bytes()) {
+        for capture in m.captures {
+            let node = capture.node;
+            prototypes.insert(source[node.start_byte()..node.end_byte()].to_string());
+        }
+    }
+}
+
+/// Records every `typedef` name and `struct`, `union`, and `enum` tag a file defines
+fn collect_type_names(source: &str, root: Node, type_names: &mut HashSet<String>) {
+    static QUERY: LazyLock<Query> = LazyLock::new(|| {
+        Query::new(
+            tree_sitter_c::language(),
+            r#"
+            (type_definition declarator: (type_identifier) @type)
+            (struct_specifier name: (type_identifier) @type body: (_))
+            (union_specifier name: (type_identifier) @type body: (_))
+            (enum_specifier name: (type_identifier) @type body: (_))
+            "#,
+        )
+        .unwrap()
+    });
+    let query = &*QUERY;
+
+    let mut query_cursor = QueryCursor::new();
+    for m in query_cursor.matches(query, root, source.as_bytes()) {
+        for capture in m.captures {
+            let node = capture.node;
+            type_names.insert(source[node.start_byte()..node.end_byte()].to_string());
+        }
+    }
+}
+
+/// Which of snake_case, camelCase, PascalCase, and SCREAMING_SNAKE_CASE a name is written in, a
+/// single lowercase word like `node` is both of the first two
+fn naming_cases(
+    name: &str,
+    screaming_snake_case_regex: &Regex,
+    lower_snake_case_regex: &Regex,
+    camel_case_regex: &Regex,
+) -> [bool; 4] {
+    let is_word = |name: &str| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    };
+    let is_camel = |name: &str| is_word(name) || camel_case_regex.is_match(name);
+    let mut chars = name.chars();
+    let is_pascal = chars.next().is_some_and(|first| {
+        let rest = chars.as_str();
+        first.is_ascii_uppercase()
+            && rest.chars().any(|c| c.is_ascii_lowercase())
+            && is_camel(&format!("{}{rest}", first.to_ascii_lowercase()))
+    });
+    [
+        is_word(name) || lower_snake_case_regex.is_match(name),
+        is_camel(name),
+        is_pascal,
+        screaming_snake_case_regex.is_match(name),
+    ]
+}
+
+/// Records every variable and parameter a file declares, globals and locals alike
+fn collect_variables<'a>(
+    file: &'a Path,
+    source: &str,
+    root: Node,
+    variables: &mut Vec<Variable<'a>>,
+) {
+    static QUERY: LazyLock<Query> = LazyLock::new(|| {
+        Query::new(
+            tree_sitter_c::language(),
+            r#"
+            (declaration declarator: (_) @declarator)
+            (parameter_declaration declarator: (_) @declarator)
+            "#,
+        )
+        .unwrap()
+    });
+    let query = &*QUERY;
+
+    let mut query_cursor = QueryCursor::new();
+    for m in query_cursor.matches(query, root, source.as_bytes()) {
+        for capture in m.captures {
+            if !declares_variable(capture.node) {
+                continue;
+            }
+            let identifier = declared_identifier(capture.node);
+            let range = identifier.range();
+            variables.push(Variable {
+                file,
+                range,
+                name: source[range.start_byte..range.end_byte].to_string(),
+                text: source
+                    .lines()
+                    .nth(range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+            });
+        }
+    }
+}
+
+/// Whether a function-like macro's body is wrapped in parentheses (or is a single call) and every use
+/// of a parameter is too, so neither picks up the precedence of the code around it. Statement-like
+/// bodies such as `do { ... } while (0)` are left alone
+fn is_macro_parenthesized(macro_def: Node, source: &str) -> bool {
+    let Some(value) = macro_def.child_by_field_name("value") else {
+        return true;
+    };
+    let body = source[value.start_byte()..value.end_byte()].trim();
+    if body.is_empty() || body.starts_with("do") || body.starts_with('{') {
+        return true;
+    }
+
+    let callee_len = body
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(body.len());
+    // stringizing and token pasting build a single token, which parentheses would only break up
+    if !body.contains('#')
+        && !is_wrapped_in_parens(body)
+        && !is_wrapped_in_parens(&body[callee_len..])
+    {
+        return false;
+    }
+
+    let is_space = |c: char| c.is_whitespace() || c == '\\';
+    let parameters = macro_def.child_by_field_name("parameters").unwrap();
+    let mut cursor = parameters.walk();
+    let names = parameters
+        .named_children(&mut cursor)
+        .filter(|parameter| parameter.kind() == "identifier")
+        .map(|parameter| &source[parameter.start_byte()..parameter.end_byte()])
+        .collect::<Vec<&str>>();
+    names.iter().all(|name| {
+        let use_regex = Regex::new(&format!(r"\b{}\b", regex::escape(name))).unwrap();
+        let parenthesized = use_regex.find_iter(body).all(|m| {
+            let before = body[..m.start()].trim_end_matches(is_space);
+            let after = body[m.end()..].trim_start_matches(is_space);
+            // `#x` and `x ## y` work on the tokens themselves, and a comma is the loosest operator
+            before.ends_with('#')
+                || after.starts_with("##")
+                || ((before.ends_with('(') || before.ends_with(','))
+                    && (after.starts_with(')') || after.starts_with(',')))
+        });
+        parenthesized
+    })
+}
+
+/// Whether `text` starts with `(` and the matching `)` is its last character
+fn is_wrapped_in_parens(text: &str) -> bool {
+    if !text.starts_with('(') {
+        return false;
+    }
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            return i == text.len() - 1;
+        }
+    }
+    false
+}
+
+/// Heuristic for a comment whose body is C code rather than prose: it has to contain a `;` or
+/// brace, be more than one token, and parse cleanly as the statements of a function body
+fn is_commented_out_code(comment: &str) -> bool {
+    let body = match comment.strip_prefix("/*") {
+        Some(block) => block
+            .strip_suffix("*/")
+            .unwrap_or(block)
+            .lines()
+            .map(|line| line.trim_start().strip_prefix('*').unwrap_or(line))
+            .collect::<Vec<&str>>()
+            .join("\n"),
+        None => comment.trim_start_matches('/').to_string(),
+    };
+
+    if !body.contains([';', '{', '}']) {
+        return false;
+    }
+
+    let mut parser = c_parser();
+    let wrapped = format!("void f() {{\n{body}\n}}");
+    let tree = parser.parse(&wrapped, None).unwrap();
+    // the wrapper itself is the six tokens `void f ( ) { }`
+    !tree.root_node().has_error() && count_tokens(tree.root_node()) > 6 + 1
+}
+
+fn count_tokens(node: Node) -> usize {
+    if node.child_count() == 0 {
+        return 1;
+    }
+    let mut cursor = node.walk();
+    let count = node.children(&mut cursor).map(count_tokens).sum();
+    count
+}
+
+/// Unwraps init, pointer, and array declarators down to the name being declared
+fn declared_identifier(declarator: Node) -> Node {
+    let mut node = declarator;
+    while let Some(inner) = inner_declarator(node) {
+        node = inner;
+    }
+    node
+}
+
+fn inner_declarator(declarator: Node) -> Option<Node> {
+    match declarator.kind() {
+        // `(*fp)` has no field name for what is inside the parentheses
+        "parenthesized_declarator" => declarator.named_child(0),
+        _ => declarator.child_by_field_name("declarator"),
+    }
+}
+
+/// Whether a declarator declares a variable rather than a function. `int foo(int);` and
+/// `int *foo(int);` are prototypes, while `int (*fp)(int);` is a variable holding a function pointer
+fn declares_variable(declarator: Node) -> bool {
+    match declarator.kind() {
+        "function_declarator" => {
+            let inner = declarator.child_by_field_name("declarator").unwrap();
+            inner.kind() != "identifier"
+        }
+        _ => match inner_declarator(declarator) {
+            Some(inner) => declares_variable(inner),
+            None => true,
+        },
+    }
+}
+
+/// How to bound a `gets` or `scanf` call that reads a string of any length into a local array,
+/// `None` if the call is bounded or reads into something else
+fn unbounded_read_fix(call: Node, name: &str, source: &str) -> Option<String> {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    let arguments = call.child_by_field_name("arguments")?;
+    let mut cursor = arguments.walk();
+    let arguments = arguments
+        .named_children(&mut cursor)
+        .filter(|argument| argument.kind() != "comment")
+        .collect::<Vec<Node>>();
+    let buffers = match name {
+        "gets" => vec![*arguments.first()?],
+        "scanf" => {
+            let format = arguments
+                .first()
+                .filter(|format| format.kind() == "string_literal")?;
+            let conversion_regex =
+                Regex::new(r"%%|%(\*)?(\d*)(?:hh|h|ll|l|L|j|z|t)?(\[\^?\]?[^\]]*\]|[a-zA-Z])")
+                    .unwrap();
+            let mut buffers = vec![];
+            let mut next = 1;
+            for conversion in conversion_regex.captures_iter(text(*format)) {
+                // `%%` matches a literal percent sign and `%*s` reads without storing
+                if &conversion[0] == "%%" || conversion.get(1).is_some() {
+                    continue;
+                }
+                let argument = arguments.get(next);
+                next += 1;
+                if conversion[3].starts_with(['s', '[']) && conversion[2].is_empty() {
+                    buffers.extend(argument);
+                }
+            }
+            buffers
+        }
+        _ => return None,
+    };
+
+    buffers.into_iter().find_map(|buffer| {
+        if buffer.kind() != "identifier" {
+            return None;
+        }
+        let buffer_name = text(buffer);
+        // array parameters are pointers, only arrays declared in the function have a fixed size
+        let declaration = local_declaration(buffer, buffer_name, source)
+            .filter(|declaration| declaration.kind() == "declaration")?;
+        let mut cursor = declaration.walk();
+        let array = declaration
+            .children_by_field_name("declarator", &mut cursor)
+            .map(|declarator| match declarator.kind() {
+                "init_declarator" => declarator.child_by_field_name("declarator").unwrap(),
+                _ => declarator,
+            })
+            .find(|declarator| text(declared_identifier(*declarator)) == buffer_name)
+            .filter(|declarator| declarator.kind() == "array_declarator")?;
+        let size = array
+            .child_by_field_name("size")
+            .filter(|size| size.kind() == "number_literal")
+            .and_then(|size| text(size).parse::<usize>().ok())
+            .filter(|&size| size > 1);
+        Some(match (name, size) {
+            ("gets", _) => format!("use fgets({buffer_name}, sizeof {buffer_name}, stdin)"),
+            (_, Some(size)) => format!("give the conversion a width, such as %{}s", size - 1),
+            (_, None) => "give the conversion a width".to_string(),
+        })
+    })
+}
+
+/// Whether a definition of `main` is `int main(void)` or `int main(int argc, char *argv[])`, with any
+/// parameter names and `char **argv` allowed. `int main()` is left to the empty parameter list check
+fn is_standard_main(definition: Node, source: &str) -> bool {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    let Some(declarator) = definition
+        .child_by_field_name("declarator")
+        .filter(|declarator| declarator.kind() == "function_declarator")
+    else {
+        return false;
+    };
+    let parameters = declarator.child_by_field_name("parameters").unwrap();
+    let mut cursor = parameters.walk();
+    let parameters = parameters
+        .named_children(&mut cursor)
+        .filter(|parameter| parameter.kind() != "comment")
+        .collect::<Vec<Node>>();
+    let has_type = |parameter: Node, ty: &str| {
+        parameter.kind() == "parameter_declaration"
+            && parameter
+                .child_by_field_name("type")
+                .is_some_and(|parameter_type| text(parameter_type) == ty)
+    };
+    let returns_int = definition
+        .child_by_field_name("type")
+        .is_some_and(|ty| text(ty) == "int");
+    returns_int
+        && match parameters[..] {
+            [] => true,
+            [parameter] => {
+                has_type(parameter, "void") && parameter.child_by_field_name("declarator").is_none()
+            }
+            [argc, argv] => {
+                // `char *argv[]` and `char **argv` are both a pointer around the named declarator
+                let argv_declarator = argv
+                    .child_by_field_name("declarator")
+                    .filter(|declarator| declarator.kind() == "pointer_declarator")
+                    .and_then(|declarator| declarator.child_by_field_name("declarator"))
+                    .filter(|declarator| {
+                        matches!(declarator.kind(), "array_declarator" | "pointer_declarator")
+                    })
+                    .and_then(|declarator| declarator.child_by_field_name("declarator"));
+                has_type(argc, "int")
+                    && argc
+                        .child_by_field_name("declarator")
+                        .is_some_and(|declarator| declarator.kind() == "identifier")
+                    && has_type(argv, "char")
+                    && argv_declarator.is_some_and(|declarator| declarator.kind() == "identifier")
+            }
+            _ => false,
+        }
+}
+
+/// Whether `name` at `node` refers to a parameter or a non-`static` local of the enclosing function
+fn is_local_variable(node: Node, name: &str, source: &str) -> bool {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    // `static` locals outlive the function
+    local_declaration(node, name, source).is_some_and(|declaration| {
+        let mut cursor = declaration.walk();
+        let is_static = declaration
+            .children(&mut cursor)
+            .any(|child| child.kind() == "storage_class_specifier" && text(child) == "static");
+        !is_static
+    })
+}
+
+/// The `declaration` or `parameter_declaration` that `name` at `node` refers to within the enclosing
+/// function, found by searching the declarations of each enclosing block from the innermost out
+fn local_declaration<'t>(node: Node<'t>, name: &str, source: &str) -> Option<Node<'t>> {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    let declares = |declaration: Node| {
+        let mut cursor = declaration.walk();
+        let declared = declaration
+            .children_by_field_name("declarator", &mut cursor)
+            .any(|declarator| text(declared_identifier(declarator)) == name);
+        declared
+    };
+
+    let mut scope = node;
+    while let Some(parent) = scope.parent() {
+        match parent.kind() {
+            "compound_statement" => {
+                let mut cursor = parent.walk();
+                let found = parent
+                    .children(&mut cursor)
+                    .take_while(|child| child.start_byte() < scope.start_byte())
+                    .filter(|child| child.kind() == "declaration")
+                    .filter(|&declaration| declares(declaration))
+                    .last();
+                if found.is_some() {
+                    return found;
+                }
+            }
+            "for_statement" => {
+                let found = parent
+                    .child_by_field_name("initializer")
+                    .filter(|initializer| initializer.kind() == "declaration")
+                    .filter(|&declaration| declares(declaration));
+                if found.is_some() {
+                    return found;
+                }
+            }
+            "function_definition" => {
+                let mut declarator = parent.child_by_field_name("declarator");
+                while let Some(d) = declarator {
+                    if let Some(parameters) = d.child_by_field_name("parameters") {
+                        let mut cursor = parameters.walk();
+                        let found = parameters
+                            .children(&mut cursor)
+                            .filter(|parameter| parameter.kind() == "parameter_declaration")
+                            .find(|&parameter| declares(parameter));
+                        return found;
+                    }
+                    declarator = d.child_by_field_name("declarator");
+                }
+                return None;
+            }
+            _ => {}
+        }
+        scope = parent;
+    }
+
+    None
+}
+
+/// Whether an array size is known at compile time. Besides literals and `sizeof`, identifiers count
+/// if they are macros defined in the file or are SCREAMING_SNAKE_CASE like a macro or enum constant
+/// from a header would be
+fn is_constant_expression(
+    node: Node,
+    source: &str,
+    macros: &HashSet<&str>,
+    screaming_snake_case_regex: &Regex,
+) -> bool {
+    match node.kind() {
+        "number_literal" | "char_literal" | "sizeof_expression" => true,
+        "identifier" => {
+            let name = &source[node.start_byte()..node.end_byte()];
+            macros.contains(name) || screaming_snake_case_regex.is_match(name)
+        }
+        "binary_expression" | "unary_expression" | "parenthesized_expression" => {
+            let mut cursor = node.walk();
+            let is_constant = node.named_children(&mut cursor).all(|child| {
+                is_constant_expression(child, source, macros, screaming_snake_case_regex)
+            });
+            is_constant
+        }
+        _ => false,
+    }
+}
+
+/// The last word of the type a declared identifier's declaration gives it, such as `int` for
+/// `unsigned int`
+fn declared_type<'s>(identifier: Node, source: &'s str) -> Option<&'s str> {
+    let mut declaration = identifier.parent().unwrap();
+    if declaration.kind() == "init_declarator" {
+        declaration = declaration.parent().unwrap();
+    }
+    let ty = declaration.child_by_field_name("type")?;
+    source[ty.start_byte()..ty.end_byte()]
+        .split_whitespace()
+        .last()
+}
+
+/// Whether a declared identifier belongs to a declaration whose type is `float` or `double`
+fn is_float_declaration(identifier: Node, source: &str) -> bool {
+    matches!(declared_type(identifier, source), Some("float" | "double"))
+}
+
+/// Whether a declared identifier belongs to a declaration whose type is a built in integer type
+fn is_integer_declaration(identifier: Node, source: &str) -> bool {
+    matches!(
+        declared_type(identifier, source),
+        Some("int" | "long" | "short" | "char" | "unsigned" | "signed" | "size_t")
+    )
+}
+
+/// Whether a declared identifier belongs to a declaration whose type is `unsigned` or `size_t`
+fn is_unsigned_declaration(identifier: Node, source: &str) -> bool {
+    let mut declaration = identifier.parent().unwrap();
+    if declaration.kind() == "init_declarator" {
+        declaration = declaration.parent().unwrap();
+    }
+    declaration.child_by_field_name("type").is_some_and(|ty| {
+        source[ty.start_byte()..ty.end_byte()]
+            .split_whitespace()
+            .any(|word| matches!(word, "unsigned" | "size_t"))
+    })
+}
+
+/// Whether the value of an expression ends up in a `float` or `double`, by initializing one,
+/// being assigned to one, or being returned from a function that returns one
+fn is_stored_as_float(node: Node, source: &str, float_identifiers: &HashSet<&str>) -> bool {
+    let mut value = node;
+    while let Some(parent) = value
+        .parent()
+        .filter(|parent| parent.kind() == "parenthesized_expression")
+    {
+        value = parent;
+    }
+    let Some(parent) = value.parent() else {
+        return false;
+    };
+    match parent.kind() {
+        "init_declarator" => {
+            let declarator = parent.child_by_field_name("declarator").unwrap();
+            parent.child_by_field_name("value") == Some(value)
+                && declarator.kind() == "identifier"
+                && is_float_declaration(declarator, source)
+        }
+        "assignment_expression" => {
+            let left = parent.child_by_field_name("left").unwrap();
+            let operator = parent.child_by_field_name("operator").unwrap();
+            parent.child_by_field_name("right") == Some(value)
+                && operator.kind() == "="
+                && left.kind() == "identifier"
+                && float_identifiers.contains(&source[left.start_byte()..left.end_byte()])
+        }
+        "return_statement" => {
+            let mut function = parent;
+            while function.kind() != "function_definition" {
+                match function.parent() {
+                    Some(next) => function = next,
+                    None => return false,
+                }
+            }
+            // `double *f()` returns a pointer, its declarator isn't the function declarator itself
+            let declarator = function.child_by_field_name("declarator").unwrap();
+            let ty = function.child_by_field_name("type").unwrap();
+            declarator.kind() == "function_declarator"
+                && matches!(
+                    source[ty.start_byte()..ty.end_byte()]
+                        .split_whitespace()
+                        .last(),
+                    Some("float" | "double")
+                )
+        }
+        _ => false,
+    }
+}
+
+fn decision_point<'a>(file: &'a Path, source: &str, node: Node, what: &str) -> Lint<'a> {
+    let range = node.range();
+    Lint {
+        rule: Rule::Complexity,
+        file,
+        range,
+        message: format!("Counted {what} decision point"),
+        text: source
+            .lines()
+            .nth(range.start_point.row)
+            .unwrap()
+            .to_string(),
+        sublints: None,
+    }
+}
+
+/// Finds the `&&`, `||`, and ternary decision points nested anywhere inside an expression
+fn count_decisions_expression<'a>(
+    file: &'a Path,
+    source: &str,
+    node: Node,
+    decisions: &mut Vec<Lint<'a>>,
+) {
+    match node.kind() {
+        "binary_expression" => {
+            let operator = node.child_by_field_name("operator").unwrap();
+            if matches!(operator.kind(), "&&" | "||") {
+                decisions.push(decision_point(file, source, operator, operator.kind()));
+            }
+        }
+        "conditional_expression" => {
+            decisions.push(decision_point(file, source, node, "ternary"));
+        }
+        _ => {}
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        count_decisions_expression(file, source, child, decisions);
+    }
+}
+
+/// Correctness checks for every statement of a function body. Unlike the line count, this also
+/// visits statements under labels and preprocessor conditionals
+fn lint_function_body<'a>(
+    file: &'a Path,
+    source: &str,
+    node: Node,
+    args: &Args,
+    addressed: &HashSet<&str>,
+    lints: &mut Vec<Lint<'a>>,
+) {
+    if node.kind() == "compound_statement" {
+        lint_unreachable(file, source, node, lints);
+        lint_uninitialized(file, source, node, lints);
+        if args.warn_uaf {
+            lint_use_after_free(file, source, node, lints);
+        }
+        if args.warn_dead_stores {
+            lint_dead_stores(file, source, node, addressed, lints);
+        }
+    }
+    if node.kind() == "if_statement" {
+        let consequence = node.child_by_field_name("consequence").unwrap();
+        let alternative = node.child_by_field_name("alternative");
+        // the code in the else could just follow the if, one level of nesting shallower
+        if let Some(alternative) = alternative.filter(|_| always_terminates(consequence)) {
+            let range = alternative.range();
+            lints.push(Lint {
+                rule: Rule::RedundantElse,
+                file,
+                range,
+                message: "Redundant else after early return".to_string(),
+                text: source
+                    .lines()
+                    .nth(range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                sublints: None,
+            });
+        }
+    }
+    if node.kind() == "while_statement" {
+        let condition = node.child_by_field_name("condition").unwrap();
+        let body = node.child_by_field_name("body").unwrap();
+        lint_infinite_loop(file, source, condition, &[body], lints);
+    }
+    if node.kind() == "for_statement" {
+        if let Some(condition) = node.child_by_field_name("condition") {
+            let body = node.child_by_field_name("body").unwrap();
+            let update = node.child_by_field_name("update");
+            let repeated = [Some(body), update]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<Node>>();
+            lint_infinite_loop(file, source, condition, &repeated, lints);
+        }
+    }
+    if node.kind() == "return_statement" {
+        let returns_value = node.named_child(0).is_some();
+        if let Some(returns_void) = returns_void(node, source) {
+            if returns_value == returns_void {
+                let range = node.range();
+                lints.push(Lint {
+                    rule: Rule::ReturnValue,
+                    file,
+                    range,
+                    message: if returns_void {
+                        "void function should not return a value"
+                    } else {
+                        "non-void function should return a value"
                     }
-                }
-                _ => {}
+                    .to_string(),
+                    text: source
+                        .lines()
+                        .nth(range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    sublints: None,
+                });
+            }
+        }
+
+        // the local's lifetime ends with the function, leaving the caller a dangling pointer
+        let value = node.child(1).unwrap();
+        if value.kind() == "pointer_expression" && value.child(0).unwrap().kind() == "&" {
+            let argument = value.child_by_field_name("argument").unwrap();
+            let name = &source[argument.start_byte()..argument.end_byte()];
+            if argument.kind() == "identifier" && is_local_variable(node, name, source) {
+                let range = value.range();
+                lints.push(Lint {
+                    rule: Rule::ReturnLocalAddress,
+                    file,
+                    range,
+                    message: "Returning address of local variable".to_string(),
+                    text: source
+                        .lines()
+                        .nth(range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    sublints: None,
+                });
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        lint_function_body(file, source, child, args, addressed, lints);
+    }
+}
+
+/// Flags the first statement of a block directly following a return/break/continue, which can
+/// never run
+fn lint_unreachable<'a>(file: &'a Path, source: &str, block: Node, lints: &mut Vec<Lint<'a>>) {
+    let mut terminator = None;
+    let mut cursor = block.walk();
+    for node in block.children(&mut cursor) {
+        if node.kind() == "labeled_statement" {
+            // a label can be jumped to, so the code after it is reachable again
+            terminator = None;
+        }
+        if let Some(stmt) = terminator {
+            if node.is_named() && node.kind() != "comment" {
+                let range = node.range();
+                lints.push(Lint {
+                    rule: Rule::UnreachableCode,
+                    file,
+                    range,
+                    message: format!("Unreachable code after {stmt}"),
+                    text: source
+                        .lines()
+                        .nth(range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    sublints: None,
+                });
+                return;
             }
         }
+        match node.kind() {
+            "return_statement" => terminator = Some("return"),
+            "break_statement" => terminator = Some("break"),
+            "continue_statement" => terminator = Some("continue"),
+            _ => {}
+        }
     }
 }
 
@@ -197,35 +3899,49 @@ fn count_lines_statement<'a>(
     source: &str,
     node: Node,
     sublints: &mut Vec<Lint<'a>>,
-) -> usize {
-    let mut linecount = 0;
+    decisions: &mut Vec<Lint<'a>>,
+) -> LineCount {
+    let mut linecount = LineCount::default();
     match node.kind() {
         "declaration" => {
-            let declarator = node.child_by_field_name("declarator");
-            if let Some(d) = declarator {
-                if d.kind() == "init_declarator" {
-                    let range = d.range();
-                    let value = range.end_point.row - range.start_point.row + 1;
-                    linecount += value;
-                    sublints.push(Lint {
-                        file,
-                        range,
-                        message: format!(
-                            "Counted definition for {value} line{}",
-                            if value != 1 { "s" } else { "" }
-                        ),
-                        text: source
-                            .lines()
-                            .nth(range.start_point.row)
-                            .unwrap()
-                            .to_string(),
-                        sublints: None,
-                    });
+            // every line from the first initialized declarator to the end of the last counts, so
+            // an initializer split over several lines, like a ternary, counts each of its lines
+            let mut cursor = node.walk();
+            let initialized = node
+                .children_by_field_name("declarator", &mut cursor)
+                .filter(|d| d.kind() == "init_declarator")
+                .collect::<Vec<Node>>();
+            if let (Some(first), Some(last)) = (initialized.first(), initialized.last()) {
+                for d in &initialized {
+                    count_decisions_expression(file, source, *d, decisions);
                 }
+                let range = Range {
+                    start_byte: first.start_byte(),
+                    end_byte: last.end_byte(),
+                    start_point: first.start_position(),
+                    end_point: last.end_position(),
+                };
+                let value = range.end_point.row - range.start_point.row + 1;
+                linecount.definitions += value;
+                sublints.push(Lint {
+                    rule: Rule::FunctionLength,
+                    file,
+                    range,
+                    message: format!(
+                        "Counted definition for {value} line{}",
+                        if value != 1 { "s" } else { "" }
+                    ),
+                    text: source
+                        .lines()
+                        .nth(range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    sublints: None,
+                });
             }
         }
         "if_statement" => {
-            linecount += count_lines_if_statement(file, source, node, sublints);
+            linecount += count_lines_if_statement(file, source, node, sublints, decisions);
         }
         "preproc_ifdef" => {
             let name = node.child_by_field_name("name").unwrap();
@@ -233,7 +3949,7 @@ fn count_lines_statement<'a>(
             if text != "DEBUG" {
                 let mut cursor = node.walk();
                 for node in node.children(&mut cursor).skip(2) {
-                    linecount += count_lines_statement(file, source, node, sublints);
+                    linecount += count_lines_statement(file, source, node, sublints, decisions);
                 }
             }
         }
@@ -241,8 +3957,9 @@ fn count_lines_statement<'a>(
             let condition = node.child_by_field_name("condition").unwrap();
             let condition_range = condition.range();
             let value = condition_range.end_point.row - condition_range.start_point.row + 1;
-            linecount += value;
+            linecount.conditions += value;
             sublints.push(Lint {
+                rule: Rule::FunctionLength,
                 file,
                 range: condition_range,
                 message: format!(
@@ -256,32 +3973,50 @@ fn count_lines_statement<'a>(
                     .to_string(),
                 sublints: None,
             });
+            decisions.push(decision_point(file, source, node, "while"));
+            count_decisions_expression(file, source, condition, decisions);
 
             let body = node.child_by_field_name("body").unwrap();
-            linecount += count_lines_statement(file, source, body, sublints);
+            linecount += count_lines_statement(file, source, body, sublints, decisions);
         }
         "do_statement" => {
             let body = node.child_by_field_name("body").unwrap();
-            linecount += count_lines_statement(file, source, body, sublints);
+            let body_sublints = sublints.len();
+            linecount += count_lines_statement(file, source, body, sublints, decisions);
 
+            // in a compact `do { i++; } while (i < n);` the condition shares its row with the last
+            // statement of the body, which has already been counted
+            let last_counted_row = sublints[body_sublints..]
+                .iter()
+                .map(|sublint| sublint.range.end_point.row)
+                .max();
             let condition = node.child_by_field_name("condition").unwrap();
             let condition_range = condition.range();
-            let value = condition_range.end_point.row - condition_range.start_point.row + 1;
-            linecount += value;
-            sublints.push(Lint {
-                file,
-                range: condition_range,
-                message: format!(
-                    "Counted do/while condition for {value} line{}",
-                    if value != 1 { "s" } else { "" }
-                ),
-                text: source
-                    .lines()
-                    .nth(condition_range.start_point.row)
-                    .unwrap()
-                    .to_string(),
-                sublints: None,
-            });
+            let first_row = match last_counted_row {
+                Some(row) if row >= condition_range.start_point.row => row + 1,
+                _ => condition_range.start_point.row,
+            };
+            let value = (condition_range.end_point.row + 1).saturating_sub(first_row);
+            linecount.conditions += value;
+            if value > 0 {
+                sublints.push(Lint {
+                    rule: Rule::FunctionLength,
+                    file,
+                    range: condition_range,
+                    message: format!(
+                        "Counted do/while condition for {value} line{}",
+                        if value != 1 { "s" } else { "" }
+                    ),
+                    text: source
+                        .lines()
+                        .nth(condition_range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    sublints: None,
+                });
+            }
+            decisions.push(decision_point(file, source, node, "do/while"));
+            count_decisions_expression(file, source, condition, decisions);
         }
         "for_statement" => {
             let num_children = node.child_count();
@@ -292,8 +4027,9 @@ fn count_lines_statement<'a>(
             let range = first_node.range();
             let value =
                 penultimate_node.range().end_point.row - first_node.range().start_point.row + 1;
-            linecount += value;
+            linecount.conditions += value;
             sublints.push(Lint {
+                rule: Rule::FunctionLength,
                 file,
                 range,
                 message: format!(
@@ -307,15 +4043,21 @@ fn count_lines_statement<'a>(
                     .to_string(),
                 sublints: None,
             });
+            decisions.push(decision_point(file, source, node, "for"));
+            let mut cursor = node.walk();
+            for header in node.children(&mut cursor).take(num_children - 1) {
+                count_decisions_expression(file, source, header, decisions);
+            }
 
-            linecount += count_lines_statement(file, source, body, sublints);
+            linecount += count_lines_statement(file, source, body, sublints, decisions);
         }
         "switch_statement" => {
             let condition = node.child_by_field_name("condition").unwrap();
             let condition_range = condition.range();
             let value = condition_range.end_point.row - condition_range.start_point.row + 1;
-            linecount += value;
+            linecount.conditions += value;
             sublints.push(Lint {
+                rule: Rule::FunctionLength,
                 file,
                 range: condition_range,
                 message: format!(
@@ -329,16 +4071,18 @@ fn count_lines_statement<'a>(
                     .to_string(),
                 sublints: None,
             });
+            count_decisions_expression(file, source, condition, decisions);
 
             let body = node.child_by_field_name("body").unwrap();
-            linecount += count_lines_statement(file, source, body, sublints);
+            linecount += count_lines_statement(file, source, body, sublints, decisions);
         }
         "expression_statement" => {
             let expression = node.child(0).unwrap();
             let expression_range = expression.range();
             let value = expression_range.end_point.row - expression_range.start_point.row + 1;
-            linecount += value;
+            linecount.expressions += value;
             sublints.push(Lint {
+                rule: Rule::FunctionLength,
                 file,
                 range: expression_range,
                 message: format!(
@@ -352,46 +4096,377 @@ fn count_lines_statement<'a>(
                     .to_string(),
                 sublints: None,
             });
+            count_decisions_expression(file, source, expression, decisions);
         }
         "case_statement" => {
+            // `default:` has no value and is taken when no other case is, so it adds no path
+            if node.child_by_field_name("value").is_some() {
+                decisions.push(decision_point(file, source, node, "case"));
+            }
+
             let mut count = |node: Node| {
                 let mut cursor = node.walk();
                 for node in node.children(&mut cursor) {
                     if node.kind() != "break_statement" {
-                        linecount += count_lines_statement(file, source, node, sublints);
+                        linecount += count_lines_statement(file, source, node, sublints, decisions);
                     }
                 }
             };
 
-            let expression = node.child(node.child_count() - 1).unwrap();
-            if expression.kind() == "compound_statement" {
-                count(expression);
-            } else {
-                count(node);
+            let expression = node.child(node.child_count() - 1).unwrap();
+            if expression.kind() == "compound_statement" {
+                count(expression);
+            } else {
+                count(node);
+            }
+        }
+        "break_statement" => {
+            let range = node.range();
+            linecount.jumps += 1;
+            sublints.push(Lint {
+                rule: Rule::FunctionLength,
+                file,
+                range,
+                message: "Counted break statement for 1 line".to_string(),
+                text: source
+                    .lines()
+                    .nth(range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                sublints: None,
+            });
+        }
+        "continue_statement" => {
+            let range = node.range();
+            linecount.jumps += 1;
+            sublints.push(Lint {
+                rule: Rule::FunctionLength,
+                file,
+                range,
+                message: "Counted continue statement for 1 line".to_string(),
+                text: source
+                    .lines()
+                    .nth(range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                sublints: None,
+            });
+        }
+        "else_clause" => {
+            linecount +=
+                count_lines_statement(file, source, node.child(1).unwrap(), sublints, decisions);
+        }
+        "return_statement" => {
+            let identifier = node.child(1).unwrap();
+            let identifier_range = identifier.range();
+            linecount.jumps += 1;
+            sublints.push(Lint {
+                rule: Rule::FunctionLength,
+                file,
+                range: identifier_range,
+                message: "Counted return statement for 1 line".to_string(),
+                text: source
+                    .lines()
+                    .nth(identifier_range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                sublints: None,
+            });
+            count_decisions_expression(file, source, node, decisions);
+        }
+        "compound_statement" => {
+            linecount += count_lines_compound_statement(file, source, node, sublints, decisions);
+        }
+        _ => {}
+    }
+    linecount
+}
+
+fn count_lines_compound_statement<'a>(
+    file: &'a Path,
+    source: &str,
+    node: Node,
+    sublints: &mut Vec<Lint<'a>>,
+    decisions: &mut Vec<Lint<'a>>,
+) -> LineCount {
+    let mut linecount = LineCount::default();
+
+    let mut cursor = node.walk();
+    for node in node.children(&mut cursor) {
+        linecount += count_lines_statement(file, source, node, sublints, decisions);
+    }
+
+    linecount
+}
+
+/// Flags locals declared without an initializer in this block whose first use after the
+/// declaration reads them. Any write, even in a nested branch, counts as initializing the variable,
+/// and so does taking its address (`scanf("%d", &x)`), which keeps this from guessing about branches
+fn lint_uninitialized<'a>(file: &'a Path, source: &str, block: Node, lints: &mut Vec<Lint<'a>>) {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    let mut uninitialized: Vec<&str> = vec![];
+
+    let mut cursor = block.walk();
+    for statement in block.named_children(&mut cursor) {
+        if !uninitialized.is_empty() {
+            find_uninitialized_reads(file, source, statement, &mut uninitialized, lints);
+        }
+
+        if statement.kind() == "declaration" {
+            let mut specifier_cursor = statement.walk();
+            let has_storage_class = statement
+                .children(&mut specifier_cursor)
+                .any(|child| child.kind() == "storage_class_specifier");
+            let mut declarator_cursor = statement.walk();
+            for declarator in statement.children_by_field_name("declarator", &mut declarator_cursor)
+            {
+                // `static` and `extern` variables are zero initialized or initialized elsewhere
+                if declarator.kind() == "identifier" && !has_storage_class {
+                    uninitialized.push(text(declarator));
+                }
+            }
+        }
+    }
+}
+
+fn find_uninitialized_reads<'a>(
+    file: &'a Path,
+    source: &str,
+    node: Node,
+    uninitialized: &mut Vec<&str>,
+    lints: &mut Vec<Lint<'a>>,
+) {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    let tracked = |node: Node, uninitialized: &Vec<&str>| {
+        node.kind() == "identifier" && uninitialized.contains(&text(node))
+    };
+
+    match node.kind() {
+        "assignment_expression" => {
+            let left = node.child_by_field_name("left").unwrap();
+            let operator = node.child_by_field_name("operator").unwrap();
+            if operator.kind() == "=" && tracked(left, uninitialized) {
+                // `x = x + 1` still reads `x` before writing it
+                let right = node.child_by_field_name("right").unwrap();
+                find_uninitialized_reads(file, source, right, uninitialized, lints);
+                uninitialized.retain(|name| *name != text(left));
+                return;
+            }
+        }
+        "pointer_expression" => {
+            let argument = node.child_by_field_name("argument").unwrap();
+            if node.child(0).unwrap().kind() == "&" && tracked(argument, uninitialized) {
+                uninitialized.retain(|name| *name != text(argument));
+                return;
+            }
+        }
+        // `sizeof x` never evaluates `x`
+        "sizeof_expression" => return,
+        "declaration" => {
+            // a nested block declaring the same name shadows it, so stop guessing about it
+            let mut cursor = node.walk();
+            for declarator in node.children_by_field_name("declarator", &mut cursor) {
+                let name = text(declared_identifier(declarator));
+                uninitialized.retain(|tracked| *tracked != name);
+            }
+        }
+        "identifier" if tracked(node, uninitialized) => {
+            let name = text(node);
+            let range = node.range();
+            lints.push(Lint {
+                rule: Rule::UninitializedUse,
+                file,
+                range,
+                message: format!("Variable '{name}' may be used before initialization"),
+                text: source
+                    .lines()
+                    .nth(range.start_point.row)
+                    .unwrap()
+                    .to_string(),
+                sublints: None,
+            });
+            uninitialized.retain(|tracked| *tracked != name);
+            return;
+        }
+        _ => {}
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        find_uninitialized_reads(file, source, child, uninitialized, lints);
+    }
+}
+
+/// Flags pointers passed to `free` by a statement of this block that a later statement of the
+/// block uses before assigning them again. Only `free`s directly in the block are tracked, and taking
+/// the pointer's address stops tracking it, so a `free` in one branch never causes a lint
+fn lint_use_after_free<'a>(file: &'a Path, source: &str, block: Node, lints: &mut Vec<Lint<'a>>) {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    let mut freed: Vec<&str> = vec![];
+
+    let mut cursor = block.walk();
+    for statement in block.named_children(&mut cursor) {
+        if !freed.is_empty() {
+            find_freed_uses(file, source, statement, &mut freed, lints);
+        }
+
+        let call = statement
+            .named_child(0)
+            .filter(|_| statement.kind() == "expression_statement")
+            .filter(|expression| expression.kind() == "call_expression");
+        if let Some(call) = call {
+            let function = call.child_by_field_name("function").unwrap();
+            let arguments = call.child_by_field_name("arguments").unwrap();
+            if text(function) == "free" && arguments.named_child_count() == 1 {
+                let pointer = arguments.named_child(0).unwrap();
+                if pointer.kind() == "identifier" && !freed.contains(&text(pointer)) {
+                    freed.push(text(pointer));
+                }
+            }
+        }
+    }
+}
+
+/// Flags a statement of this block assigning a local that a later statement of the block assigns
+/// again before anything reads it. Statements that mention the variable in any other way, such as a
+/// branch that might assign it, count as reads, and variables whose address is taken anywhere in the
+/// function (`addressed`) are skipped
+fn lint_dead_stores<'a>(
+    file: &'a Path,
+    source: &str,
+    block: Node,
+    addressed: &HashSet<&str>,
+    lints: &mut Vec<Lint<'a>>,
+) {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    let mentions = |node: Node, name: &str| {
+        let mut identifiers = HashSet::new();
+        collect_identifiers(node, source, &mut identifiers);
+        identifiers.contains(name)
+    };
+
+    // each local's last store that nothing has read yet
+    let mut stored: Vec<(&str, Node)> = vec![];
+    let mut cursor = block.walk();
+    for statement in block.named_children(&mut cursor) {
+        // (name, what is stored in it, the store as a whole, whether the variable is a local)
+        let mut stores = vec![];
+        match statement.kind() {
+            "expression_statement" => {
+                let assignment = statement
+                    .named_child(0)
+                    .filter(|expression| expression.kind() == "assignment_expression");
+                if let Some(assignment) = assignment {
+                    let left = assignment.child_by_field_name("left").unwrap();
+                    let operator = assignment.child_by_field_name("operator").unwrap();
+                    if left.kind() == "identifier" && operator.kind() == "=" {
+                        let right = assignment.child_by_field_name("right").unwrap();
+                        let local = is_local_variable(assignment, text(left), source);
+                        stores.push((text(left), right, assignment, local));
+                    }
+                }
+            }
+            "declaration" => {
+                let mut specifier_cursor = statement.walk();
+                let local = !statement
+                    .children(&mut specifier_cursor)
+                    .any(|child| child.kind() == "storage_class_specifier");
+                let mut declarator_cursor = statement.walk();
+                for declarator in
+                    statement.children_by_field_name("declarator", &mut declarator_cursor)
+                {
+                    let name = text(declared_identifier(declarator));
+                    // a new variable of the same name isn't the old one being overwritten
+                    stored.retain(|(tracked, _)| *tracked != name);
+                    if let Some(value) = declarator.child_by_field_name("value") {
+                        stores.push((name, value, declarator, local));
+                    }
+                }
+            }
+            "labeled_statement" => stored.clear(),
+            _ => {}
+        }
+
+        if stores.is_empty() {
+            stored.retain(|(name, _)| !mentions(statement, name));
+            continue;
+        }
+        for (name, value, store, local) in stores {
+            stored.retain(|(tracked, _)| !mentions(value, tracked));
+            if let Some(index) = stored.iter().position(|(tracked, _)| *tracked == name) {
+                let (_, earlier) = stored.remove(index);
+                let range = earlier.range();
+                lints.push(Lint {
+                    rule: Rule::DeadStore,
+                    file,
+                    range,
+                    message: format!(
+                        "Value assigned to '{name}' is never used before being overwritten"
+                    ),
+                    text: source
+                        .lines()
+                        .nth(range.start_point.row)
+                        .unwrap()
+                        .to_string(),
+                    sublints: None,
+                });
+            }
+            // globals and `static` locals might be read by any function called in between
+            if local && !addressed.contains(name) {
+                stored.push((name, store));
+            }
+        }
+    }
+}
+
+fn find_freed_uses<'a>(
+    file: &'a Path,
+    source: &str,
+    node: Node,
+    freed: &mut Vec<&str>,
+    lints: &mut Vec<Lint<'a>>,
+) {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    let tracked =
+        |node: Node, freed: &Vec<&str>| node.kind() == "identifier" && freed.contains(&text(node));
+
+    match node.kind() {
+        "assignment_expression" => {
+            let left = node.child_by_field_name("left").unwrap();
+            let operator = node.child_by_field_name("operator").unwrap();
+            if operator.kind() == "=" && tracked(left, freed) {
+                // `p = p->next` still reads `p` before writing it
+                let right = node.child_by_field_name("right").unwrap();
+                find_freed_uses(file, source, right, freed, lints);
+                freed.retain(|name| *name != text(left));
+                return;
             }
         }
-        "break_statement" => {
-            let range = node.range();
-            linecount += 1;
-            sublints.push(Lint {
-                file,
-                range,
-                message: "Counted break statement for 1 line".to_string(),
-                text: source
-                    .lines()
-                    .nth(range.start_point.row)
-                    .unwrap()
-                    .to_string(),
-                sublints: None,
-            });
+        "pointer_expression" => {
+            let argument = node.child_by_field_name("argument").unwrap();
+            if node.child(0).unwrap().kind() == "&" && tracked(argument, freed) {
+                freed.retain(|name| *name != text(argument));
+                return;
+            }
         }
-        "continue_statement" => {
+        // `sizeof *p` never evaluates `p`
+        "sizeof_expression" => return,
+        "declaration" => {
+            // a nested block declaring the same name shadows it, so stop guessing about it
+            let mut cursor = node.walk();
+            for declarator in node.children_by_field_name("declarator", &mut cursor) {
+                let name = text(declared_identifier(declarator));
+                freed.retain(|tracked| *tracked != name);
+            }
+        }
+        "identifier" if tracked(node, freed) => {
+            let name = text(node);
             let range = node.range();
-            linecount += 1;
-            sublints.push(Lint {
+            lints.push(Lint {
+                rule: Rule::UseAfterFree,
                 file,
                 range,
-                message: "Counted continue statement for 1 line".to_string(),
+                message: format!("Possible use-after-free of '{name}'"),
                 text: source
                     .lines()
                     .nth(range.start_point.row)
@@ -399,48 +4474,16 @@ fn count_lines_statement<'a>(
                     .to_string(),
                 sublints: None,
             });
-        }
-        "else_clause" => {
-            linecount += count_lines_statement(file, source, node.child(1).unwrap(), sublints);
-        }
-        "return_statement" => {
-            let identifier = node.child(1).unwrap();
-            let identifier_range = identifier.range();
-            linecount += 1;
-            sublints.push(Lint {
-                file,
-                range: identifier_range,
-                message: "Counted return statement for 1 line".to_string(),
-                text: source
-                    .lines()
-                    .nth(identifier_range.start_point.row)
-                    .unwrap()
-                    .to_string(),
-                sublints: None,
-            });
-        }
-        "compound_statement" => {
-            linecount += count_lines_compound_statement(file, source, node, sublints);
+            freed.retain(|tracked| *tracked != name);
+            return;
         }
         _ => {}
     }
-    return linecount;
-}
-
-fn count_lines_compound_statement<'a>(
-    file: &'a Path,
-    source: &str,
-    node: Node,
-    sublints: &mut Vec<Lint<'a>>,
-) -> usize {
-    let mut linecount = 0;
 
     let mut cursor = node.walk();
-    for node in node.children(&mut cursor) {
-        linecount += count_lines_statement(file, source, node, sublints);
+    for child in node.children(&mut cursor) {
+        find_freed_uses(file, source, child, freed, lints);
     }
-
-    return linecount;
 }
 
 fn count_lines_if_statement<'a>(
@@ -448,14 +4491,16 @@ fn count_lines_if_statement<'a>(
     source: &str,
     node: Node,
     sublints: &mut Vec<Lint<'a>>,
-) -> usize {
-    let mut linecount = 0;
+    decisions: &mut Vec<Lint<'a>>,
+) -> LineCount {
+    let mut linecount = LineCount::default();
 
     let condition = node.child_by_field_name("condition").unwrap();
     let condition_range = condition.range();
     let value = condition_range.end_point.row - condition_range.start_point.row + 1;
-    linecount += value;
+    linecount.conditions += value;
     sublints.push(Lint {
+        rule: Rule::FunctionLength,
         file,
         range: condition_range,
         message: format!(
@@ -469,79 +4514,1394 @@ fn count_lines_if_statement<'a>(
             .to_string(),
         sublints: None,
     });
+    decisions.push(decision_point(file, source, node, "if"));
+    count_decisions_expression(file, source, condition, decisions);
 
     let consequence = node.child_by_field_name("consequence").unwrap();
-    linecount += count_lines_statement(file, source, consequence, sublints);
+    linecount += count_lines_statement(file, source, consequence, sublints, decisions);
 
     if let Some(alt) = node.child_by_field_name("alternative") {
-        linecount += count_lines_statement(file, source, alt, sublints);
+        linecount += count_lines_statement(file, source, alt, sublints, decisions);
+    }
+
+    linecount
+}
+
+/// Flags a loop whose condition only reads variables that the code run on every iteration
+/// (`repeated`) never modifies. Loops that can leave some other way, and conditions that call a
+/// function, which could return something different each time, are skipped
+fn lint_infinite_loop<'a>(
+    file: &'a Path,
+    source: &str,
+    condition: Node,
+    repeated: &[Node],
+    lints: &mut Vec<Lint<'a>>,
+) {
+    let mut read = HashSet::new();
+    collect_identifiers(condition, source, &mut read);
+    if read.is_empty() || contains_kind(condition, &["call_expression"]) {
+        return;
+    }
+    let exits = ["break_statement", "return_statement", "goto_statement"];
+    if repeated.iter().any(|&node| contains_kind(node, &exits)) {
+        return;
+    }
+
+    // `while (i++ < n)` changes its variable in the condition itself
+    let mut modified = HashSet::new();
+    collect_modified(condition, source, &mut modified);
+    for &node in repeated {
+        collect_modified(node, source, &mut modified);
+    }
+    if read.is_disjoint(&modified) {
+        let range = condition.range();
+        lints.push(Lint {
+            rule: Rule::InfiniteLoop,
+            file,
+            range,
+            message:
+                "Loop condition variables are never modified in the body, possible infinite loop"
+                    .to_string(),
+            text: source
+                .lines()
+                .nth(range.start_point.row)
+                .unwrap()
+                .to_string(),
+            sublints: None,
+        });
+    }
+}
+
+/// Names whose address is taken with `&` anywhere under a node
+fn address_taken<'s>(node: Node, source: &'s str) -> HashSet<&'s str> {
+    let mut pointers = vec![];
+    collect_kind(node, "pointer_expression", &mut pointers);
+    pointers
+        .into_iter()
+        .filter(|pointer| pointer.child(0).unwrap().kind() == "&")
+        .map(|pointer| {
+            let argument = pointer.child_by_field_name("argument").unwrap();
+            &source[argument.start_byte()..argument.end_byte()]
+        })
+        .collect()
+}
+
+fn collect_identifiers<'s>(node: Node, source: &'s str, identifiers: &mut HashSet<&'s str>) {
+    if node.kind() == "identifier" {
+        identifiers.insert(&source[node.start_byte()..node.end_byte()]);
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_identifiers(child, source, identifiers);
+    }
+}
+
+/// Names written to by an assignment, `++`/`--`, or having their address taken (`scanf("%d", &x)`)
+fn collect_modified<'s>(node: Node, source: &'s str, modified: &mut HashSet<&'s str>) {
+    let target = match node.kind() {
+        "assignment_expression" => node.child_by_field_name("left"),
+        "update_expression" => node.child_by_field_name("argument"),
+        "pointer_expression" if node.child(0).unwrap().kind() == "&" => {
+            node.child_by_field_name("argument")
+        }
+        _ => None,
+    };
+    // writing to `a[i]` or `p->next` changes what a condition on `a` or `p` sees
+    if let Some(target) = target {
+        collect_identifiers(target, source, modified);
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_modified(child, source, modified);
+    }
+}
+
+/// Heuristic for allocations a function neither frees nor hands to its caller. Each allocation is
+/// followed through the variable it is stored in, which has to be passed to `free`, returned, or
+/// stored through a pointer, array element, or field. Allocations returned or passed on directly
+/// are handed off too
+fn lint_leak<'a>(file: &'a Path, source: &str, function: Node, lints: &mut Vec<Lint<'a>>) {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    let is_wrapper =
+        |node: &Node| matches!(node.kind(), "cast_expression" | "parenthesized_expression");
+    // `(int *)(p)` is still `p`
+    fn unwrapped(node: Node) -> Node {
+        let mut value = node;
+        while matches!(value.kind(), "cast_expression" | "parenthesized_expression") {
+            value = value
+                .child_by_field_name("value")
+                .or_else(|| value.named_child(0))
+                .unwrap();
+        }
+        value
+    }
+    let body = function.child_by_field_name("body").unwrap();
+    let mut calls = vec![];
+    collect_kind(body, "call_expression", &mut calls);
+    let callee = |call: Node| text(call.child_by_field_name("function").unwrap());
+
+    // names whose memory is freed, returned, or stored somewhere that outlives the function
+    let mut released = HashSet::new();
+    for call in calls.iter().filter(|&&call| callee(call) == "free") {
+        let argument = call
+            .child_by_field_name("arguments")
+            .unwrap()
+            .named_child(0);
+        released.extend(argument.map(unwrapped).map(text));
+    }
+    let mut returns = vec![];
+    collect_kind(body, "return_statement", &mut returns);
+    released.extend(
+        returns
+            .iter()
+            .filter_map(|r| r.named_child(0))
+            .map(unwrapped)
+            .map(text),
+    );
+    let mut assignments = vec![];
+    collect_kind(body, "assignment_expression", &mut assignments);
+    released.extend(
+        assignments
+            .iter()
+            .filter(|assignment| {
+                assignment.child_by_field_name("left").unwrap().kind() != "identifier"
+            })
+            .map(|assignment| unwrapped(assignment.child_by_field_name("right").unwrap()))
+            .map(text),
+    );
+
+    for call in calls
+        .iter()
+        .filter(|&&call| matches!(callee(call), "malloc" | "calloc" | "realloc"))
+    {
+        let mut value = *call;
+        while let Some(parent) = value.parent().filter(is_wrapper) {
+            value = parent;
+        }
+        let Some(parent) = value.parent() else {
+            continue;
+        };
+        let name = match parent.kind() {
+            "init_declarator" => text(declared_identifier(parent)),
+            "assignment_expression" => {
+                let left = parent.child_by_field_name("left").unwrap();
+                if left.kind() != "identifier" {
+                    continue;
+                }
+                text(left)
+            }
+            _ => continue,
+        };
+        if released.contains(name) {
+            continue;
+        }
+        let range = call.range();
+        lints.push(Lint {
+            rule: Rule::MemoryLeak,
+            text: source
+                .lines()
+                .nth(range.start_point.row)
+                .unwrap()
+                .to_string(),
+            message: "Allocated memory is never freed in this function".to_string(),
+            range,
+            file,
+            sublints: None,
+        })
+    }
+}
+
+/// The type an allocation's result points to, with whitespace removed, when it initializes or is
+/// assigned to a pointer variable whose declaration is in view. Casts of the result are looked through
+fn allocated_type(call: Node, source: &str) -> Option<String> {
+    let text = |node: Node| &source[node.start_byte()..node.end_byte()];
+    let mut value = call;
+    let mut parent = call.parent()?;
+    while matches!(
+        parent.kind(),
+        "cast_expression" | "parenthesized_expression"
+    ) {
+        value = parent;
+        parent = parent.parent()?;
+    }
+    let (declaration, declarator) = match parent.kind() {
+        "init_declarator" => (parent.parent()?, parent.child_by_field_name("declarator")?),
+        "assignment_expression" if parent.child_by_field_name("right") == Some(value) => {
+            let left = parent.child_by_field_name("left")?;
+            let name = text(left);
+            let declaration = local_declaration(call, name, source)?;
+            let mut cursor = declaration.walk();
+            let declarator = declaration
+                .children_by_field_name("declarator", &mut cursor)
+                .find(|&declarator| text(declared_identifier(declarator)) == name)?;
+            (declaration, declarator)
+        }
+        _ => return None,
+    };
+
+    let mut depth = 0;
+    let mut node = declarator;
+    while let Some(inner) = inner_declarator(node) {
+        match node.kind() {
+            "pointer_declarator" => depth += 1,
+            "init_declarator" | "parenthesized_declarator" => {}
+            // arrays of pointers and function pointers aren't simple enough to check
+            _ => return None,
+        }
+        node = inner;
+    }
+    if depth == 0 {
+        return None;
+    }
+    let ty = declaration.child_by_field_name("type")?;
+    Some(text(ty).replace(char::is_whitespace, "") + &"*".repeat(depth - 1))
+}
+
+/// Every node of `kind` in `node`'s subtree, including `node` itself
+fn collect_kind<'t>(node: Node<'t>, kind: &str, found: &mut Vec<Node<'t>>) {
+    if node.kind() == kind {
+        found.push(node);
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_kind(child, kind, found);
+    }
+}
+
+fn contains_kind(node: Node, kinds: &[&str]) -> bool {
+    if kinds.contains(&node.kind()) {
+        return true;
+    }
+    let mut cursor = node.walk();
+    let contains = node
+        .children(&mut cursor)
+        .any(|child| contains_kind(child, kinds));
+    contains
+}
+
+/// Whether every path through a statement ends in a `return`, `break`, `continue`, or `goto`
+fn always_terminates(node: Node) -> bool {
+    match node.kind() {
+        "return_statement" | "break_statement" | "continue_statement" | "goto_statement" => true,
+        "compound_statement" => {
+            let mut cursor = node.walk();
+            let terminates = node.named_children(&mut cursor).any(always_terminates);
+            terminates
+        }
+        "if_statement" => {
+            always_terminates(node.child_by_field_name("consequence").unwrap())
+                && node
+                    .child_by_field_name("alternative")
+                    .is_some_and(always_terminates)
+        }
+        "else_clause" => always_terminates(node.named_child(0).unwrap()),
+        _ => false,
+    }
+}
+
+/// A file's contents, exiting with a diagnostic naming the file if it can't be read
+fn read_source(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Failed to read `{}`: {e}", path.display());
+        std::process::exit(2);
+    })
+}
+
+fn discover_files(path: PathBuf, args: &Args) -> HashSet<PathBuf> {
+    let source = read_source(&path);
+    discover_files_with_source(path, &source, args)
+}
+
+/// Like `discover_files`, but for a file whose contents are already known (e.g. read from stdin)
+fn discover_files_with_source(path: PathBuf, source: &str, args: &Args) -> HashSet<PathBuf> {
+    let mut fileset = HashSet::new();
+    visit_includes(path, source, args, &mut fileset);
+    fileset
+}
+
+/// Adds `path` and every file it includes, directly or through other files, to `fileset`. Files
+/// already in it aren't followed again, so headers that include each other don't recurse forever
+fn visit_includes(path: PathBuf, source: &str, args: &Args, fileset: &mut HashSet<PathBuf>) {
+    let path = canonicalize(path);
+    if !fileset.insert(path.clone()) {
+        return;
+    }
+
+    for (_, include_path) in quoted_includes(&path, source, args) {
+        if let Some(include_path) = include_path {
+            if !fileset.contains(&include_path) {
+                let source = read_source(&include_path);
+                visit_includes(include_path, &source, args, fileset);
+            }
+        }
+    }
+}
+
+/// Every `#include "..."` at the top level of a file as written, along with the file it resolves
+/// to, or `None` if it isn't found anywhere
+fn quoted_includes(path: &Path, source: &str, args: &Args) -> Vec<(String, Option<PathBuf>)> {
+    let parent = path.parent().unwrap();
+    let mut includes = vec![];
+
+    let mut parser = c_parser();
+    let tree = parser.parse(source, None).unwrap();
+    let root_node = tree.root_node();
+    let mut cursor = root_node.walk();
+    for node in root_node.children(&mut cursor) {
+        if node.kind() == "preproc_include" {
+            let path_node = node.child_by_field_name("path").unwrap();
+            if path_node.kind() == "string_literal" {
+                let range = path_node.range();
+                let include = &source[range.start_byte + 1..range.end_byte - 1];
+                // like `cc -I`, the including file's directory is searched before the include paths
+                let directory = iter::once(parent)
+                    .chain(args.include_path.iter().map(PathBuf::as_path))
+                    .find(|directory| directory.join(include).is_file());
+                if args.trace_includes {
+                    match directory {
+                        Some(directory) => eprintln!(
+                            "{}: \"{}\" found in {}",
+                            path.display(),
+                            include,
+                            directory.display()
+                        ),
+                        None => eprintln!("{}: \"{}\" not found", path.display(), include),
+                    }
+                }
+                includes.push((
+                    include.to_string(),
+                    directory.map(|directory| canonicalize(directory.join(include))),
+                ));
+            }
+        }
+    }
+    includes
+}
+
+/// Prints an `including -> included` line for each include reachable from `path`, each file's
+/// includes only once. Includes that aren't found are printed as written and marked unresolved
+fn print_includes(path: PathBuf, source: &str, args: &Args, printed: &mut HashSet<PathBuf>) {
+    let path = canonicalize(path);
+    if !printed.insert(path.clone()) {
+        return;
+    }
+    for (include, include_path) in quoted_includes(&path, source, args) {
+        match include_path {
+            Some(include_path) => {
+                println!("{} -> {}", path.display(), include_path.display());
+                let source = read_source(&include_path);
+                print_includes(include_path, &source, args, printed);
+            }
+            None => println!("{} -> \"{}\" (unresolved)", path.display(), include),
+        }
+    }
+}
+
+/// `range` with its columns counted in characters rather than bytes, the byte offsets are unchanged
+fn character_columns(source: &str, range: Range) -> Range {
+    let column = |byte: usize, point: Point| {
+        Point::new(point.row, source[byte - point.column..byte].chars().count())
+    };
+    Range {
+        start_point: column(range.start_byte, range.start_point),
+        end_point: column(range.end_byte, range.end_point),
+        ..range
+    }
+}
+
+/// Collapses different spellings of the same file (`./foo.h`, `foo.h`, `dir/../foo.h`) into one
+/// path, kept relative to the working directory where possible so lints stay readable
+fn canonicalize(path: PathBuf) -> PathBuf {
+    match std::env::current_dir() {
+        Ok(cwd) => relative_to(path, &cwd),
+        Err(_) => fs::canonicalize(&path).unwrap_or(path),
+    }
+}
+
+/// Canonical form of `path` relative to `root`, stepping out of `root` with `..` when needed. Paths
+/// that don't exist on disk, like `<stdin>`, are returned untouched
+fn relative_to(path: PathBuf, root: &Path) -> PathBuf {
+    let Ok(canonical) = fs::canonicalize(&path) else {
+        return path;
+    };
+    let Ok(root) = fs::canonicalize(root) else {
+        return canonical;
+    };
+
+    let shared = canonical
+        .components()
+        .zip(root.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative = PathBuf::new();
+    for _ in root.components().skip(shared) {
+        relative.push("..");
+    }
+    relative.extend(canonical.components().skip(shared));
+    relative
+}
+
+/// Canonical form of `path` relative to `base` when it is inside `base`, and absolute otherwise.
+/// Paths that don't exist on disk, like `<stdin>`, are returned untouched
+fn inside(path: PathBuf, base: &Path) -> PathBuf {
+    let Ok(canonical) = fs::canonicalize(&path) else {
+        return path;
+    };
+    match fs::canonicalize(base) {
+        Ok(base) => match canonical.strip_prefix(&base) {
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => canonical,
+        },
+        Err(_) => canonical,
+    }
+}
+
+/// Replaces each `@path` argument with the files listed in `path`, one per line like gcc's response
+/// files. Blank lines and lines starting with `#` are skipped
+fn expand_response_files(files: &[String]) -> Vec<String> {
+    let mut expanded = vec![];
+    for file in files {
+        let Some(path) = file.strip_prefix('@') else {
+            expanded.push(file.clone());
+            continue;
+        };
+        let contents = fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("Failed to read response file `{path}`: {e}");
+            std::process::exit(2);
+        });
+        expanded.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string),
+        );
+    }
+    expanded
+}
+
+fn expand_globs(files: &[String]) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    for file in files {
+        // literal paths are passed through untouched, even if they don't exist
+        if !file.contains(['*', '?', '[']) {
+            paths.push(PathBuf::from(file));
+            continue;
+        }
+
+        let matches = glob::glob(file).unwrap_or_else(|e| {
+            eprintln!("Invalid glob pattern `{file}`: {e}");
+            std::process::exit(2);
+        });
+        let mut matched = matches.filter_map(Result::ok).collect::<Vec<PathBuf>>();
+        if matched.is_empty() {
+            eprintln!("No files match `{file}`");
+        }
+        matched.sort();
+        paths.append(&mut matched);
+    }
+
+    paths
+}
+
+/// Prints how many lints of each rule fired in each file to stderr, keeping stdout parseable
+fn print_stats(lints: &[Lint]) {
+    let mut counts: BTreeMap<(&Path, &str), usize> = BTreeMap::new();
+    for lint in lints {
+        *counts.entry((lint.file, lint.rule.code())).or_default() += 1;
+    }
+
+    let width = counts
+        .keys()
+        .map(|(file, _)| file.to_str().unwrap().len())
+        .max()
+        .unwrap_or(0)
+        .max("total".len());
+    for ((file, code), count) in counts {
+        eprintln!("{:<width$} {:<26} {count}", file.to_str().unwrap(), code);
+    }
+    eprintln!("{:<width$} {:<26} {}", "total", "", lints.len());
+}
+
+/// Writes lint counts per file, rule, and severity, and whether the run passed `--fail-on`. Every
+/// linted file, rule, and severity is listed, with a count of 0 if nothing fired
+fn write_summary(path: &Path, files: &[PathBuf], lints: &[Lint], passed: bool) {
+    let mut per_file: BTreeMap<&str, usize> = files
+        .iter()
+        .map(|file| (file.to_str().unwrap(), 0))
+        .collect();
+    let mut per_rule: BTreeMap<&str, usize> =
+        Rule::ALL.iter().map(|rule| (rule.code(), 0)).collect();
+    let mut per_severity: BTreeMap<&str, usize> = [Severity::Error, Severity::Warning]
+        .iter()
+        .map(|severity| (severity.name(), 0))
+        .collect();
+    for lint in lints {
+        *per_file.entry(lint.file.to_str().unwrap()).or_default() += 1;
+        *per_rule.entry(lint.rule.code()).or_default() += 1;
+        *per_severity
+            .entry(lint.rule.info().severity.name())
+            .or_default() += 1;
+    }
+    let summary = json!({
+        "total": lints.len(),
+        "passed": passed,
+        "files": per_file,
+        "rules": per_rule,
+        "severities": per_severity,
+    });
+    fs::write(path, format!("{summary:#}\n")).unwrap_or_else(|e| {
+        eprintln!("Failed to write summary `{}`: {e}", path.display());
+        std::process::exit(2);
+    });
+}
+
+/// How many lines a lint may have moved from its baseline entry and still be suppressed by it, so
+/// editing code above an old lint doesn't bring it back
+const BASELINE_LINE_TOLERANCE: usize = 10;
+
+fn write_baseline(path: &Path, lints: &[Lint]) {
+    let entries = lints
+        .iter()
+        .map(|lint| {
+            json!({
+                "file": lint.file.to_str().unwrap(),
+                "rule": lint.rule.code(),
+                "message": lint.message,
+                "line": lint.range.start_point.row + 1,
+                "text": lint.text,
+            })
+        })
+        .collect::<Vec<Value>>();
+    let baseline = serde_json::to_string_pretty(&entries).unwrap();
+    fs::write(path, baseline + "\n").unwrap_or_else(|e| {
+        eprintln!("Failed to write baseline `{}`: {e}", path.display());
+        std::process::exit(2);
+    });
+}
+
+/// Drops every lint that was already present when the baseline was written. Each baseline entry
+/// suppresses at most one lint with the same file, rule, and message, preferring one on an identical
+/// line of code and then the nearest one
+fn apply_baseline(path: &Path, lints: &mut Vec<Lint>) {
+    let baseline = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|source| serde_json::from_str::<Vec<Value>>(&source).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to read baseline `{}`: {e}", path.display());
+            std::process::exit(2);
+        });
+
+    let mut suppressed = vec![false; lints.len()];
+    for entry in baseline {
+        let line = entry["line"].as_u64().unwrap_or(0) as usize;
+        let nearest = lints
+            .iter()
+            .enumerate()
+            .filter(|(i, lint)| {
+                !suppressed[*i]
+                    && entry["file"] == lint.file.to_str().unwrap()
+                    && entry["rule"] == lint.rule.code()
+                    && entry["message"] == lint.message.as_str()
+            })
+            .map(|(i, lint)| {
+                let moved = entry["text"] != lint.text.as_str();
+                (i, moved, line.abs_diff(lint.range.start_point.row + 1))
+            })
+            .filter(|(_, _, distance)| *distance <= BASELINE_LINE_TOLERANCE)
+            .min_by_key(|(_, moved, distance)| (*moved, *distance));
+        if let Some((i, _, _)) = nearest {
+            suppressed[i] = true;
+        }
+    }
+
+    let mut suppressed = suppressed.into_iter();
+    lints.retain(|_| !suppressed.next().unwrap());
+}
+
+/// Name of the config files that turn rules on and off for the directory they are in
+const CONFIG_FILE_NAME: &str = ".cse2331lint.toml";
+
+/// Rules turned off for files in `directory`, by its config file and those of every directory above
+/// it. Configs are applied from the root down, so the nearest config has the last word on a rule.
+/// Turning a rule on only undoes a config above, a rule the command line leaves off stays off
+fn disabled_rules(
+    directory: &Path,
+    args: &Args,
+    resolved: &mut HashMap<PathBuf, HashSet<Rule>>,
+) -> HashSet<Rule> {
+    if let Some(disabled) = resolved.get(directory) {
+        return disabled.clone();
     }
+    let mut disabled = directory
+        .parent()
+        .map(|parent| disabled_rules(parent, args, resolved))
+        .unwrap_or_default();
+    let path = directory.join(CONFIG_FILE_NAME);
+    if path.is_file() {
+        for (rule, enabled) in read_config(&path) {
+            if enabled {
+                if !rule.enabled(args) {
+                    eprintln!(
+                        "Warning: `{} = true` in `{}` has no effect, the rule is off on the command line",
+                        rule.code(),
+                        path.display()
+                    );
+                }
+                disabled.remove(&rule);
+            } else {
+                disabled.insert(rule);
+            }
+        }
+    }
+    resolved.insert(directory.to_path_buf(), disabled.clone());
+    disabled
+}
+
+/// The `[rules]` table of a config file, each rule code set to `true` or `false`
+fn read_config(path: &Path) -> Vec<(Rule, bool)> {
+    let config = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|source| source.parse::<toml::Table>().map_err(|e| e.to_string()))
+        .and_then(|config| {
+            let Some(rules) = config.get("rules") else {
+                return Ok(vec![]);
+            };
+            rules
+                .as_table()
+                .ok_or("`rules` must be a table".to_string())?
+                .iter()
+                .map(|(code, enabled)| {
+                    let rule = parse_rule_code(code)?;
+                    let enabled = enabled
+                        .as_bool()
+                        .ok_or(format!("`{code}` must be true or false"))?;
+                    Ok((rule, enabled))
+                })
+                .collect::<Result<Vec<(Rule, bool)>, String>>()
+        });
+    config.unwrap_or_else(|e| {
+        eprintln!("Failed to read config `{}`: {e}", path.display());
+        std::process::exit(2);
+    })
+}
+
+/// The `.c` and `.h` files changed since `base` with the rows, counted from 0, of the lines added or
+/// changed in each, keyed by their canonical path
+fn changed_lines(base: &str) -> HashMap<PathBuf, Vec<(usize, usize)>> {
+    let git = |args: &[&str]| match Command::new("git").args(args).output() {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+        Ok(output) => {
+            eprintln!(
+                "Failed to find files changed since `{base}`: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            std::process::exit(2);
+        }
+        Err(e) => {
+            eprintln!("Failed to run git: {e}");
+            std::process::exit(2);
+        }
+    };
+    let root = git(&["rev-parse", "--show-toplevel"]);
+    let root = Path::new(root.trim());
+    // deleted files have nothing left to lint
+    let diff = git(&[
+        "-C",
+        &root.to_string_lossy(),
+        "diff",
+        "--unified=0",
+        "--no-color",
+        "--no-ext-diff",
+        // `diff.noprefix` and `diff.mnemonicPrefix` would change the `+++ b/` lines parsed below
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+        "--diff-filter=d",
+        base,
+        "--",
+        "*.c",
+        "*.h",
+    ]);
+
+    let hunk_regex = Regex::new(r"^@@ -\S+ \+(\d+)(?:,(\d+))? @@").unwrap();
+    let mut changed: HashMap<PathBuf, Vec<(usize, usize)>> = HashMap::new();
+    let mut current = None;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ b/") {
+            let path = root.join(path);
+            let path = fs::canonicalize(&path).unwrap_or(path);
+            changed.entry(path.clone()).or_default();
+            current = Some(path);
+        } else if let Some(hunk) = hunk_regex.captures(line) {
+            let start = hunk[1].parse::<usize>().unwrap();
+            let count = hunk
+                .get(2)
+                .map_or(1, |count| count.as_str().parse().unwrap());
+            // a hunk that only removes lines adds none
+            if let Some(path) = current.as_ref().filter(|_| count > 0) {
+                changed
+                    .get_mut(path)
+                    .unwrap()
+                    .push((start - 1, start - 1 + count));
+            }
+        }
+    }
+    changed
+}
+
+const IGNORE_FILE_NAME: &str = ".cse2331lintignore";
+
+/// Whether a `.cse2331lintignore` in the file's directory or one above it excludes the file. The
+/// nearest ignore file with a pattern matching it decides, so a `!pattern` can re-include a file
+fn is_ignored(file: &Path, ignores: &mut HashMap<PathBuf, Option<Gitignore>>) -> bool {
+    let Ok(file) = fs::canonicalize(file) else {
+        return false;
+    };
+    for directory in file.ancestors().skip(1) {
+        let ignore = ignores
+            .entry(directory.to_path_buf())
+            .or_insert_with(|| read_ignore(directory));
+        let Some(ignore) = ignore else {
+            continue;
+        };
+        let matched = ignore.matched_path_or_any_parents(&file, false);
+        if !matched.is_none() {
+            return matched.is_ignore();
+        }
+    }
+    false
+}
+
+/// The directory's ignore file, with patterns relative to the directory like a `.gitignore`
+fn read_ignore(directory: &Path) -> Option<Gitignore> {
+    let path = directory.join(IGNORE_FILE_NAME);
+    if !path.is_file() {
+        return None;
+    }
+    let (ignore, error) = Gitignore::new(&path);
+    if let Some(e) = error {
+        eprintln!("Failed to read ignore file `{}`: {e}", path.display());
+        std::process::exit(2);
+    }
+    Some(ignore)
+}
+
+/// The configuration in effect once defaults, flags, and the rules config files turn off
+/// (`disabled`) are applied, for debugging why a rule did or didn't fire
+fn effective_config(args: &Args, disabled: &HashSet<Rule>) -> Value {
+    let rules = Rule::ALL
+        .iter()
+        .map(|rule| {
+            json!({
+                "code": rule.code(),
+                "category": rule.category().name(),
+                "severity": rule.info().severity.name(),
+                "enabled": rule.enabled(args) && !disabled.contains(rule),
+            })
+        })
+        .collect::<Vec<Value>>();
 
-    return linecount;
+    json!({
+        "thresholds": {
+            "max_function_lines": MAX_FUNCTION_LINES,
+            "length_metric": format!("{:?}", args.length_metric).to_lowercase(),
+            "max_statement_lines": args.max_statement_lines,
+            "max_elseif": args.max_elseif,
+            "max_complexity": args.max_complexity,
+            "max_file_lines": args.max_file_lines,
+            "max_consecutive_blank_lines": args.max_consecutive_blank_lines,
+            "comment_gap": args.comment_gap,
+            "max_lints": args.max_lints,
+            "min_identifier_length": args.min_identifier_length,
+        },
+        "identifier_style": format!("{:?}", args.identifier_style).to_lowercase(),
+        "todo_keywords": args.todo_keywords,
+        "reserved_names": args.reserved_names,
+        "short_identifiers": args.short_identifiers,
+        "allow_global": args.allow_global,
+        "allow_repeated_format_strings": args.allow_repeated_format_strings,
+        "exclude": args.exclude,
+        "no_recurse_includes": args.no_recurse_includes,
+        "include_path": args.include_path,
+        "root": args.root,
+        "relative_to": args.relative_to,
+        "baseline": args.baseline,
+        "output": args.output,
+        "jobs": args.jobs,
+        "format": format!("{:?}", args.format).to_lowercase(),
+        "error_format": format!("{:?}", args.error_format).to_lowercase(),
+        "sort": format!("{:?}", args.sort).to_lowercase(),
+        "fail_on": format!("{:?}", args.fail_on).to_lowercase(),
+        "category_exit_codes": args.category_exit_codes,
+        "rules": rules,
+    })
 }
 
-fn discover_files(path: PathBuf) -> HashSet<PathBuf> {
-    let mut fileset = HashSet::new();
-    fileset.insert(path.clone());
-
-    let parent = path.parent().unwrap();
+/// Look up a rule by the code shown in `--list-rules`
+fn parse_rule_code(code: &str) -> Result<Rule, String> {
+    Rule::ALL
+        .into_iter()
+        .find(|rule| rule.code() == code)
+        .ok_or_else(|| format!("unknown rule `{code}`, see `--list-rules`"))
+}
 
-    let source = fs::read_to_string(path.clone()).unwrap();
-    let mut parser = tree_sitter::Parser::new();
-    parser
-        .set_language(tree_sitter_c::language())
-        .expect("Error loading C grammar");
-    let tree = parser.parse(&source, None).unwrap();
-    let root_node = tree.root_node();
-    let mut cursor = root_node.walk();
-    for node in root_node.children(&mut cursor) {
-        if node.kind() == "preproc_include" {
-            let path_node = node.child_by_field_name("path").unwrap();
-            if path_node.kind() == "string_literal" {
-                let range = path_node.range();
-                let include_path = &source[range.start_byte + 1..range.end_byte - 1];
-                if !fileset.contains(&PathBuf::from(include_path)) {
-                    let newfiles = discover_files(parent.join(include_path));
-                    fileset.extend(newfiles);
-                }
-            }
-        }
+fn list_rules() {
+    for rule in Rule::ALL {
+        let info = rule.info();
+        println!(
+            "{:<26} {:<12} {:<8} {:<4} {}",
+            info.code,
+            info.category.name(),
+            info.severity.name(),
+            if info.enabled_by_default { "on" } else { "off" },
+            info.description,
+        );
+        println!("{:<26} `{}`", "", info.message);
     }
+}
+
+fn explain(rule: Rule) {
+    let info = rule.info();
+    println!("{}: {}", info.code, info.description);
+    println!();
+    println!("{}", info.explanation);
+}
 
-    return fileset;
+fn exclude_patterns(excludes: &[String]) -> Vec<glob::Pattern> {
+    excludes
+        .iter()
+        .map(|exclude| {
+            // discovered paths are canonicalized, so `./vendor/*.h` has to match `vendor/foo.h`
+            glob::Pattern::new(exclude.trim_start_matches("./")).unwrap_or_else(|e| {
+                eprintln!("Invalid exclude pattern `{exclude}`: {e}");
+                std::process::exit(2);
+            })
+        })
+        .collect()
 }
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Files to lint
+    /// Files to lint, glob patterns such as `src/**/*.c` are expanded internally.
+    /// Use `-` to read from stdin, and `@path` to lint every file listed in `path`
     #[arg()]
     files: Vec<String>,
+
+    /// Path reported for source read from stdin, also used to resolve its `#include`s
+    #[arg(long)]
+    stdin_filename: Option<PathBuf>,
+
+    /// Report file paths relative to this directory instead of the working directory
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// Report file paths inside this directory relative to it, and every other path as absolute
+    #[arg(long, conflicts_with = "root")]
+    relative_to: Option<PathBuf>,
+
+    /// Only lint the files given, without following their `#include "..."`s to other files
+    #[arg(long)]
+    no_recurse_includes: bool,
+
+    /// Only lint `.c` and `.h` files that `git diff` reports as changed since this ref, out of the
+    /// files given or, without any, all of them
+    #[arg(long, value_name = "BASE_REF")]
+    only_changed: Option<String>,
+
+    /// With `--only-changed`, only report lints that start on a changed line
+    #[arg(long, requires = "only_changed")]
+    only_changed_lines: bool,
+
+    /// Directory searched for `#include "..."`s not found beside the including file, can be given
+    /// multiple times and is searched in order
+    #[arg(long)]
+    include_path: Vec<PathBuf>,
+
+    /// Print which file each `#include "..."` resolves to, one `including -> included` per line,
+    /// then exit without linting
+    #[arg(long)]
+    print_includes: bool,
+
+    /// Print to stderr which directory each `#include "..."` was found in
+    #[arg(long)]
+    trace_includes: bool,
+
+    /// Skip files matching this glob pattern, can be given multiple times. Excluded files are
+    /// still followed for `#include`s, so headers they include are linted unless excluded as well
+    #[arg(long)]
+    exclude: Vec<String>,
+
+    /// List every rule with its category, severity, and whether it is on by default, then exit
+    #[arg(long)]
+    list_rules: bool,
+
+    /// Explain what a rule checks, why, and how to fix what it reports, then exit
+    #[arg(long, value_parser = parse_rule_code)]
+    explain: Option<Rule>,
+
+    /// Print the configuration in effect as JSON, then exit
+    #[arg(long)]
+    config_print: bool,
+
+    /// Turn off the rule with this code, can be given multiple times
+    #[arg(long, value_parser = parse_rule_code)]
+    disable: Vec<Rule>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Write the lints to this file instead of stdout, replacing its contents
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// Order lints are reported in
+    #[arg(long, value_enum, default_value_t = SortOrder::Location)]
+    sort: SortOrder,
+
+    /// Shape of each line of text output
+    #[arg(long, value_enum, default_value_t = ErrorFormat::Default)]
+    error_format: ErrorFormat,
+
+    /// Also print where each lint's range ends, for editor integration
+    #[arg(long)]
+    show_end: bool,
+
+    /// Only print the first this many lints in text output
+    #[arg(long)]
+    max_lints: Option<usize>,
+
+    /// Reuse the lints of files that haven't changed since the last run with this directory, and
+    /// save the lints of the rest there
+    #[arg(long)]
+    cache: Option<PathBuf>,
+
+    /// Only report lints that aren't already in this baseline file
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Save every lint to this baseline file instead of reporting them
+    #[arg(long)]
+    write_baseline: Option<PathBuf>,
+
+    /// Also write a JSON summary of lint counts and whether the run passed to this file, whatever the
+    /// output format
+    #[arg(long)]
+    summary_json: Option<PathBuf>,
+
+    /// Print how many lints of each rule fired per file to stderr
+    #[arg(long)]
+    stats: bool,
+
+    /// How the lines of a function are counted for the function length limit
+    #[arg(long, value_enum, default_value_t = LengthMetric::Statements)]
+    length_metric: LengthMetric,
+
+    /// Report `if`/`else if` chains with more than this many branches comparing one variable
+    #[arg(long, default_value_t = 4)]
+    max_elseif: usize,
+
+    /// Report single statements spanning more than this many lines
+    #[arg(long, default_value_t = 5)]
+    max_statement_lines: usize,
+
+    /// Case identifiers must use, macros are SCREAMING_SNAKE_CASE regardless
+    #[arg(long, value_enum, default_value_t = IdentifierStyle::Consistent)]
+    identifier_style: IdentifierStyle,
+
+    /// Don't report repeated string literals containing `%`, such as `printf` format strings
+    #[arg(long)]
+    allow_repeated_format_strings: bool,
+
+    /// Report functions whose cyclomatic complexity is above this threshold
+    #[arg(long)]
+    max_complexity: Option<usize>,
+
+    /// Report files with more than this many lines, blank lines at the end of the file don't count
+    #[arg(long)]
+    max_file_lines: Option<usize>,
+
+    /// Lowest severity of lint that makes the linter exit with a failure
+    #[arg(long, value_enum, default_value_t = FailOn::Error)]
+    fail_on: FailOn,
+
+    /// Report lints but always exit 0, the same as `--fail-on never`
+    #[arg(long, conflicts_with = "fail_on")]
+    relative_exit: bool,
+
+    /// Exit with a bitmask of the rule categories that fired instead of 1
+    /// (1 = naming, 2 = structure, 4 = comments, 8 = correctness)
+    #[arg(long)]
+    category_exit_codes: bool,
+
+    /// Name of a global variable the assignment requires, can be given multiple times
+    #[arg(long)]
+    allow_global: Vec<String>,
+
+    /// Blank lines allowed between a function and the comment above it
+    #[arg(long, default_value_t = 0)]
+    comment_gap: usize,
+
+    /// Report runs of more than this many blank lines in a row
+    #[arg(long, default_value_t = 1)]
+    max_consecutive_blank_lines: usize,
+
+    /// Report comments that look like commented-out code (heuristic)
+    #[arg(long)]
+    warn_commented_code: bool,
+
+    /// Report switch statements without a default case
+    #[arg(long)]
+    require_switch_default: bool,
+
+    /// Report declarations that follow a statement in the same block, as C89 requires
+    #[arg(long)]
+    declarations_first: bool,
+
+    /// Report object-like macros defined as a number, which could be a `const` or `enum` instead
+    #[arg(long)]
+    prefer_const: bool,
+
+    /// Report `if`, `else`, and loop bodies that aren't enclosed in braces
+    #[arg(long)]
+    require_braces: bool,
+
+    /// Report functions that allocate memory but never free or return it (heuristic)
+    #[arg(long)]
+    warn_leaks: bool,
+
+    /// Report variables named the same as a `typedef` or a `struct`, `union`, or `enum` tag
+    #[arg(long)]
+    warn_type_shadowing: bool,
+
+    /// Report a `typedef` naming a tagged struct in a different case style than its tag
+    #[arg(long)]
+    warn_typedef_case: bool,
+
+    /// Report `malloc` and `calloc` calls whose `sizeof` names a different type than the pointer the
+    /// result is stored in points to (heuristic)
+    #[arg(long)]
+    warn_alloc_size: bool,
+
+    /// Report pointers used after being freed in the same block (heuristic)
+    #[arg(long)]
+    warn_uaf: bool,
+
+    /// Report values assigned to a local variable that are overwritten before being read
+    #[arg(long)]
+    warn_dead_stores: bool,
+
+    /// Report commas in initializer lists without a space after them or with one before them
+    #[arg(long)]
+    check_spacing: bool,
+
+    /// Report functions that are never called from any of the linted files
+    #[arg(long)]
+    warn_dead_functions: bool,
+
+    /// Report non-static functions defined in a `.c` file that no linted header has a prototype for
+    #[arg(long)]
+    require_prototypes: bool,
+
+    /// Report non-static functions in a `.c` file that only that file uses and no linted header
+    /// declares
+    #[arg(long)]
+    suggest_static: bool,
+
+    /// Number of threads to lint files with, 0 uses one per CPU
+    #[arg(long)]
+    jobs: Option<usize>,
+
+    /// Standard library names variables and functions may not reuse (comma separated)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "index,rindex,link,unlink,read,write,open,close,time,exit,abort,malloc,calloc,realloc,free,printf,scanf,puts,gets,getchar,putchar,strlen,strcpy,strcmp,strcat,memcpy,memset,abs,div,rand,srand,random,signal,kill,wait,sleep,remove,rename,log,exp,pow,sqrt,floor,ceil,round,y0,y1,j0,j1"
+    )]
+    reserved_names: Vec<String>,
+
+    /// Report variables and parameters with names shorter than this many characters, 0 disables it
+    #[arg(long, default_value_t = 0)]
+    min_identifier_length: usize,
+
+    /// Names allowed to be shorter than `--min-identifier-length`, such as loop counters
+    /// (comma separated)
+    #[arg(long, value_delimiter = ',', default_value = "i,j,k")]
+    short_identifiers: Vec<String>,
+
+    /// Comment markers reported as unresolved (comma separated, case insensitive)
+    #[arg(long, value_delimiter = ',', default_value = "TODO,FIXME,XXX,HACK")]
+    todo_keywords: Vec<String>,
 }
 
 fn main() {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    if args.relative_exit {
+        args.fail_on = FailOn::Never;
+    }
+    if args.list_rules {
+        list_rules();
+        return;
+    }
+    if let Some(rule) = args.explain {
+        explain(rule);
+        return;
+    }
+    if args.config_print {
+        // config files apply by directory, so show the ones that apply to the current directory
+        let directory = std::env::current_dir().unwrap_or_else(|e| {
+            eprintln!("Failed to read the current directory: {e}");
+            std::process::exit(2);
+        });
+        let disabled = disabled_rules(&directory, &args, &mut HashMap::new());
+        println!("{:#}", effective_config(&args, &disabled));
+        return;
+    }
+    let excludes = exclude_patterns(&args.exclude);
 
-    let mut files = args
-        .files
-        .iter()
-        .map(|file| {
-            let path = PathBuf::from(file);
-            let mut fileset = discover_files(path.clone());
-            fileset.insert(path);
-            fileset.into_iter().collect::<Vec<PathBuf>>()
+    let stdin_path = args
+        .stdin_filename
+        .clone()
+        .map(canonicalize)
+        .unwrap_or_else(|| PathBuf::from("<stdin>"));
+    let mut stdin_source = None;
+
+    if args.print_includes {
+        let mut printed = HashSet::new();
+        for path in expand_globs(&expand_response_files(&args.files)) {
+            if path == Path::new("-") {
+                let mut source = String::new();
+                io::stdin()
+                    .read_to_string(&mut source)
+                    .expect("Failed to read from stdin");
+                print_includes(stdin_path.clone(), &source, &args, &mut printed);
+                continue;
+            }
+            let source = read_source(&path);
+            print_includes(path, &source, &args, &mut printed);
+        }
+        return;
+    }
+
+    let mut files = expand_globs(&expand_response_files(&args.files))
+        .into_iter()
+        .flat_map(|path| {
+            if path == Path::new("-") {
+                let mut source = String::new();
+                io::stdin()
+                    .read_to_string(&mut source)
+                    .expect("Failed to read from stdin");
+                // without a real filename there is nowhere to resolve relative includes from
+                let fileset = if args.stdin_filename.is_some() && !args.no_recurse_includes {
+                    discover_files_with_source(stdin_path.clone(), &source, &args)
+                } else {
+                    HashSet::from([stdin_path.clone()])
+                };
+                stdin_source = Some(source);
+                return fileset.into_iter().collect::<Vec<PathBuf>>();
+            }
+
+            if args.no_recurse_includes {
+                return vec![canonicalize(path)];
+            }
+            discover_files(path, &args)
+                .into_iter()
+                .collect::<Vec<PathBuf>>()
         })
-        .flatten()
         .collect::<Vec<PathBuf>>();
 
+    let changed = args.only_changed.as_deref().map(changed_lines);
+    if let Some(changed) = &changed {
+        if args.files.is_empty() {
+            files = changed.keys().cloned().map(canonicalize).collect();
+        }
+        files.retain(|file| fs::canonicalize(file).is_ok_and(|file| changed.contains_key(&file)));
+    }
+
+    files.retain(|file| !excludes.iter().any(|pattern| pattern.matches_path(file)));
+    let mut ignores = HashMap::new();
+    files.retain(|file| !is_ignored(file, &mut ignores));
+
     let mut identifiers: Vec<Identifier> = vec![];
+    let mut functions: Vec<FunctionDefinition> = vec![];
+    let mut used_names: HashSet<String> = HashSet::new();
+    let mut prototypes: HashSet<String> = HashSet::new();
+    let mut type_names: HashSet<String> = HashSet::new();
+    let mut variables: Vec<Variable> = vec![];
     let mut lints: Vec<Lint> = vec![];
 
     files.sort();
-    for file in files.iter() {
-        let source = fs::read_to_string(file).unwrap();
-        lint(file, &source, &mut lints);
-        lint_identifiers(file, &source, &mut lints, &mut identifiers);
+    // a header included from several files (or matched by several globs) is only linted once
+    files.dedup();
+    // files are read through their real paths, but lints report them relative to `--root`
+    let display_files = match (&args.root, &args.relative_to) {
+        (Some(root), _) => files
+            .iter()
+            .map(|file| relative_to(file.clone(), root))
+            .collect::<Vec<PathBuf>>(),
+        (None, Some(base)) => files
+            .iter()
+            .map(|file| inside(file.clone(), base))
+            .collect::<Vec<PathBuf>>(),
+        (None, None) => files.clone(),
+    };
+    if let Some(jobs) = args.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to start lint threads: {e}");
+                std::process::exit(2);
+            });
+    }
+    // anything that could change a file's lints is part of its cache key. Config files only filter
+    // lints after they are cached, so they aren't
+    let config = effective_config(&args, &HashSet::new()).to_string();
+    if let Some(dir) = &args.cache {
+        fs::create_dir_all(dir).unwrap_or_else(|e| {
+            eprintln!("Failed to create cache `{}`: {e}", dir.display());
+            std::process::exit(2);
+        });
+    }
+    // files are linted in parallel, results come back in file order so output doesn't depend on it
+    let results = files
+        .par_iter()
+        .zip(display_files.par_iter())
+        .map(|(real_file, file)| {
+            let source = match &stdin_source {
+                Some(source) if *real_file == stdin_path => source.clone(),
+                _ => read_source(real_file),
+            };
+            let cache_path = args
+                .cache
+                .as_ref()
+                .map(|dir| dir.join(format!("{:016x}.json", cache_key(file, &source, &config))));
+            if let Some(results) = cache_path
+                .as_deref()
+                .and_then(|path| read_cache(path, file))
+            {
+                return results;
+            }
+            let results = lint_file(file, &source, &args);
+            if let Some(path) = cache_path {
+                // a cache that can't be written only costs time on the next run
+                if let Err(e) = fs::write(&path, results.cache_json().to_string()) {
+                    eprintln!("Failed to write cache `{}`: {e}", path.display());
+                }
+            }
+            results
+        })
+        .collect::<Vec<_>>();
+    // the files using each name, to tell which functions are only called from their own file
+    let mut users: HashMap<String, HashSet<&Path>> = HashMap::new();
+    for (results, file) in results.into_iter().zip(&display_files) {
+        for name in &results.used_names {
+            users.entry(name.clone()).or_default().insert(file);
+        }
+        lints.extend(results.lints);
+        identifiers.extend(results.identifiers);
+        functions.extend(results.functions);
+        used_names.extend(results.used_names);
+        prototypes.extend(results.prototypes);
+        type_names.extend(results.type_names);
+        variables.extend(results.variables);
+    }
+
+    // calls may come from translation units that weren't linted, which is why this is opt-in
+    for function in functions.iter().filter(|function| {
+        args.warn_dead_functions && function.name != "main" && !used_names.contains(&function.name)
+    }) {
+        lints.push(Lint {
+            rule: Rule::DeadFunction,
+            file: function.file,
+            range: function.range,
+            text: function.text.clone(),
+            message: format!("Function '{}' is defined but never used", function.name),
+            sublints: None,
+        });
+    }
+
+    // only headers reached through `#include`s are searched, so this is opt-in too
+    for function in functions.iter().filter(|function| {
+        args.require_prototypes
+            && function.file.extension().is_some_and(|ext| ext == "c")
+            && !function.is_static
+            && function.name != "main"
+            && !prototypes.contains(&function.name)
+    }) {
+        lints.push(Lint {
+            rule: Rule::MissingPrototype,
+            file: function.file,
+            range: function.range,
+            text: function.text.clone(),
+            message: format!("Function '{}' has no prototype in a header", function.name),
+            sublints: None,
+        });
+    }
+
+    // unused functions are left to `--warn-dead-functions`
+    for function in functions.iter().filter(|function| {
+        args.suggest_static
+            && function.file.extension().is_some_and(|ext| ext == "c")
+            && !function.is_static
+            && function.name != "main"
+            && !prototypes.contains(&function.name)
+            && users
+                .get(&function.name)
+                .is_some_and(|users| users.iter().all(|&user| user == function.file))
+    }) {
+        lints.push(Lint {
+            rule: Rule::SuggestStatic,
+            file: function.file,
+            range: function.range,
+            text: function.text.clone(),
+            message: format!("Function '{}' could be declared static", function.name),
+            sublints: None,
+        });
+    }
+
+    // two files may each have their own `static` function of a name, anything else fails to link
+    for (i, function) in functions.iter().enumerate() {
+        let Some(first) = functions[..i].iter().find(|other| {
+            other.name == function.name
+                && (other.file == function.file || !(other.is_static || function.is_static))
+        }) else {
+            continue;
+        };
+        lints.push(Lint {
+            rule: Rule::DuplicateFunction,
+            file: function.file,
+            range: function.range,
+            text: function.text.clone(),
+            message: format!("Function '{}' is defined more than once", function.name),
+            sublints: Some(vec![Lint {
+                rule: Rule::DuplicateFunction,
+                file: first.file,
+                range: first.range,
+                text: first.text.clone(),
+                message: "First definition".to_string(),
+                sublints: None,
+            }]),
+        });
+    }
+
+    // types from every linted file count, since headers share them between translation units
+    for variable in variables
+        .iter()
+        .filter(|variable| type_names.contains(&variable.name))
+    {
+        lints.push(Lint {
+            rule: Rule::TypeShadowing,
+            file: variable.file,
+            range: variable.range,
+            text: variable.text.clone(),
+            message: format!("Variable '{}' shadows a type name", variable.name),
+            sublints: None,
+        });
     }
 
     let snake_case_identifiers = identifiers
@@ -554,10 +5914,35 @@ fn main() {
         .filter(|i| i.case == IdentifierCase::Camel)
         .collect::<Vec<&Identifier>>();
 
-    if snake_case_identifiers.len() > 0 && camel_case_identifiers.len() > 0 {
+    // single word identifiers like `count` are both, so they are accepted by every style
+    let wrong_style: Vec<(&Identifier, &str)> = match args.identifier_style {
+        IdentifierStyle::Consistent => vec![],
+        IdentifierStyle::Snake => camel_case_identifiers
+            .iter()
+            .map(|&identifier| (identifier, "Identifier should be snake_case"))
+            .collect(),
+        IdentifierStyle::Camel => snake_case_identifiers
+            .iter()
+            .map(|&identifier| (identifier, "Identifier should be camelCase"))
+            .collect(),
+    };
+    lints.extend(wrong_style.into_iter().map(|(identifier, message)| Lint {
+        rule: Rule::IdentifierCase,
+        file: identifier.file,
+        range: identifier.range,
+        text: identifier.text.clone(),
+        message: message.to_string(),
+        sublints: None,
+    }));
+
+    if args.identifier_style == IdentifierStyle::Consistent
+        && !snake_case_identifiers.is_empty()
+        && !camel_case_identifiers.is_empty()
+    {
         let mut snake_case_sublints = snake_case_identifiers
             .iter()
             .map(|&identifier| Lint {
+                rule: Rule::IdentifierCase,
                 file: identifier.file,
                 range: identifier.range,
                 text: identifier.text.clone(),
@@ -570,6 +5955,7 @@ fn main() {
         let mut camel_case_sublints = camel_case_identifiers
             .iter()
             .map(|&identifier| Lint {
+                rule: Rule::IdentifierCase,
                 file: identifier.file,
                 range: identifier.range,
                 text: identifier.text.clone(),
@@ -581,19 +5967,657 @@ fn main() {
         lints.append(&mut camel_case_sublints);
     }
 
+    // rules turned off by config files only apply to the files under them
+    let mut resolved = HashMap::new();
+    let overrides = files
+        .iter()
+        .zip(&display_files)
+        .map(|(real_file, file)| {
+            let directory = fs::canonicalize(real_file)
+                .ok()
+                .and_then(|path| path.parent().map(Path::to_path_buf))
+                .or_else(|| std::env::current_dir().ok())
+                .unwrap();
+            (
+                file.as_path(),
+                disabled_rules(&directory, &args, &mut resolved),
+            )
+        })
+        .collect::<HashMap<&Path, HashSet<Rule>>>();
+    lints.retain(|lint| {
+        lint.rule.enabled(&args)
+            && !overrides
+                .get(lint.file)
+                .is_some_and(|disabled| disabled.contains(&lint.rule))
+    });
+
+    if let (Some(changed), true) = (&changed, args.only_changed_lines) {
+        let rows = files
+            .iter()
+            .zip(&display_files)
+            .filter_map(|(real_file, file)| {
+                let rows = changed.get(&fs::canonicalize(real_file).ok()?)?;
+                Some((file.as_path(), rows))
+            })
+            .collect::<HashMap<&Path, &Vec<(usize, usize)>>>();
+        lints.retain(|lint| {
+            let row = lint.range.start_point.row;
+            rows.get(lint.file)
+                .is_some_and(|rows| rows.iter().any(|&(start, end)| (start..end).contains(&row)))
+        });
+    }
+
+    if let Some(path) = &args.write_baseline {
+        write_baseline(path, &lints);
+        eprintln!("Wrote {} lints to {}", lints.len(), path.display());
+        return;
+    }
+    if let Some(path) = &args.baseline {
+        apply_baseline(path, &mut lints);
+    }
+
     lints.sort_by(|a, b| {
-        a.file
+        let location = a
+            .file
             .cmp(b.file)
             .then(a.range.start_point.row.cmp(&b.range.start_point.row))
-    });
-    lints.iter().for_each(|lint| {
-        println!("{}", lint.print());
-        for (i, sublint) in lint.sublints.iter().flatten().enumerate() {
-            println!("  {}) {}", i + 1, sublint.print());
+            .then(a.range.start_point.column.cmp(&b.range.start_point.column));
+        match args.sort {
+            SortOrder::Location => location,
+            SortOrder::Rule => a.rule.code().cmp(b.rule.code()).then(location),
+            SortOrder::Severity => a
+                .rule
+                .info()
+                .severity
+                .cmp(&b.rule.info().severity)
+                .then(location),
         }
     });
+    let mut lines: Vec<String> = vec![];
+    match args.format {
+        Format::Text => {
+            // lints are sorted by now, so the same first lints are always the ones shown
+            let shown = args.max_lints.unwrap_or(lints.len()).min(lints.len());
+            lints[..shown]
+                .iter()
+                .for_each(|lint| match args.error_format {
+                    ErrorFormat::Default => {
+                        lines.push(lint.print(args.show_end));
+                        for (i, sublint) in lint.sublints.iter().flatten().enumerate() {
+                            lines.push(format!("  {}) {}", i + 1, sublint.print(args.show_end)));
+                        }
+                    }
+                    ErrorFormat::Gcc => {
+                        lines.push(lint.print_gcc(lint.rule.info().severity.name()));
+                        for sublint in lint.sublints.iter().flatten() {
+                            lines.push(sublint.print_gcc("note"));
+                        }
+                    }
+                    ErrorFormat::Github => lines.push(lint.print_github()),
+                });
+            if shown < lints.len() {
+                lines.push(format!("... and {} more", lints.len() - shown));
+            }
+        }
+        Format::Sarif => lines.push(format!("{:#}", sarif(&lints))),
+        Format::Json => lines.push(format!(
+            "{:#}",
+            Value::Array(lints.iter().map(Lint::json).collect())
+        )),
+        Format::Jsonl => lines.extend(lints.iter().map(|lint| lint.json().to_string())),
+        Format::Junit => lines.push(junit(&display_files, &lints)),
+        Format::Checkstyle => lines.push(checkstyle(&display_files, &lints)),
+    }
+    match &args.output {
+        // the file is written even when there are no lints, so stale results are never left behind
+        Some(path) => {
+            let output = lines
+                .iter()
+                .map(|line| format!("{line}\n"))
+                .collect::<String>();
+            fs::write(path, output).unwrap_or_else(|e| {
+                eprintln!("Failed to write output `{}`: {e}", path.display());
+                std::process::exit(2);
+            })
+        }
+        None => lines.iter().for_each(|line| println!("{line}")),
+    }
+
+    if args.stats {
+        print_stats(&lints);
+    }
+
+    // lints below the `--fail-on` severity are still reported, they just don't fail the run
+    let failing = lints
+        .iter()
+        .filter(|lint| match args.fail_on {
+            FailOn::Error => lint.rule.info().severity == Severity::Error,
+            FailOn::Warning => true,
+            FailOn::Never => false,
+        })
+        .collect::<Vec<&Lint>>();
 
-    if lints.len() > 0 {
+    if let Some(path) = &args.summary_json {
+        write_summary(path, &display_files, &lints, failing.is_empty());
+    }
+
+    if args.category_exit_codes {
+        let code = failing
+            .iter()
+            .fold(0, |bits, lint| bits | lint.rule.category().exit_bit());
+        std::process::exit(code);
+    }
+
+    if !failing.is_empty() {
         std::process::exit(1);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory in the system's temp directory holding `files`, given as (path, contents)
+    fn temp_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("cse2421-linter-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (file, contents) in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    fn args(flags: &[&str]) -> Args {
+        Args::parse_from(iter::once("cse2421-linter").chain(flags.iter().copied()))
+    }
+
+    /// Lints of `source` as if it were the file `test.c`
+    fn lint_source(source: &str, flags: &[&str]) -> Vec<Lint<'static>> {
+        lint_file(Path::new("test.c"), source, &args(flags)).lints
+    }
+
+    /// A commented `main` with `statements` statements, each on its own line, and its braces on
+    /// lines of their own
+    fn function_with_statements(statements: usize) -> String {
+        let mut source = String::from("// Counts up\nint main(void)\n{\n  int total = 0;\n");
+        source += &"  total += 1;\n".repeat(statements - 2);
+        source += "  return total;\n}\n";
+        source
+    }
+
+    #[test]
+    fn function_braces_are_not_counted() {
+        let is_too_long = |statements: usize| {
+            lint_source(&function_with_statements(statements), &[])
+                .iter()
+                .any(|lint| lint.rule == Rule::FunctionLength)
+        };
+        assert!(!is_too_long(MAX_FUNCTION_LINES));
+        assert!(is_too_long(MAX_FUNCTION_LINES + 1));
+    }
+
+    #[test]
+    fn unnamed_parameters_are_not_short() {
+        let lints = lint_source(
+            "int f(int *, char);\n\n// Uses a short name\nint g(int *p) {\n  return *p;\n}\n",
+            &["--min-identifier-length", "2"],
+        );
+        let short = lints
+            .iter()
+            .filter(|lint| lint.rule == Rule::ShortIdentifier)
+            .map(|lint| lint.message.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(short, ["Identifier 'p' is too short (1 chars)"]);
+    }
+
+    #[test]
+    fn each_allocation_is_tracked() {
+        let lints = lint_source(
+            "#include <stdlib.h>\n\n// Keeps one allocation\nint *h(void) {\n  int *p = malloc(4);\n  \
+             int *q = malloc(8);\n  int *r = malloc(16);\n  free(r);\n  return p;\n}\n",
+            &["--warn-leaks"],
+        );
+        let leaks = lints
+            .iter()
+            .filter(|lint| lint.rule == Rule::MemoryLeak)
+            .map(|lint| lint.text.trim())
+            .collect::<Vec<&str>>();
+        assert_eq!(leaks, ["int *q = malloc(8);"]);
+    }
+
+    #[test]
+    fn returns_under_labels_and_conditionals_are_checked() {
+        let lints = lint_source(
+            "// Bails out early\nint g(int x) {\n  if (x) goto done;\n#ifdef DEBUG\n  return;\n#endif\n  \
+             return x;\ndone:\n  return;\n}\n",
+            &[],
+        );
+        let rows = lints
+            .iter()
+            .filter(|lint| lint.rule == Rule::ReturnValue)
+            .map(|lint| lint.range.start_point.row)
+            .collect::<Vec<usize>>();
+        assert_eq!(rows, [4, 8]);
+    }
+
+    #[test]
+    fn unreachable_code_under_a_label_is_found() {
+        let lints = lint_source(
+            "// Retries once\nvoid g(int x) {\n  if (x) goto retry;\n  return;\nretry: {\n    return;\n    \
+             g(0);\n  }\n}\n",
+            &[],
+        );
+        let rows = lints
+            .iter()
+            .filter(|lint| lint.rule == Rule::UnreachableCode)
+            .map(|lint| lint.range.start_point.row)
+            .collect::<Vec<usize>>();
+        assert_eq!(rows, [6]);
+    }
+
+    #[test]
+    fn returned_local_address_under_a_conditional_is_found() {
+        let lints = lint_source(
+            "// Hands out a counter\nint *g(void) {\n  int count = 0;\n#if VERBOSE\n  return &count;\n#endif\n  \
+             return 0;\n}\n",
+            &[],
+        );
+        let rows = lints
+            .iter()
+            .filter(|lint| lint.rule == Rule::ReturnLocalAddress)
+            .map(|lint| lint.range.start_point.row)
+            .collect::<Vec<usize>>();
+        assert_eq!(rows, [4]);
+    }
+
+    #[test]
+    fn redundant_else_under_a_label_is_found() {
+        let lints = lint_source(
+            "// Picks a sign\nint g(int x) {\n  if (x) goto sign;\n  return 0;\nsign:\n  if (x < 0)\n    \
+             return -1;\n  else\n    return 1;\n}\n",
+            &[],
+        );
+        let rows = lints
+            .iter()
+            .filter(|lint| lint.rule == Rule::RedundantElse)
+            .map(|lint| lint.range.start_point.row)
+            .collect::<Vec<usize>>();
+        assert_eq!(rows, [7]);
+    }
+
+    #[test]
+    fn infinite_loop_under_a_conditional_is_found() {
+        let lints = lint_source(
+            "// Waits forever when verbose\nvoid g(int n) {\n  int i = 0;\n#if VERBOSE\n  while (i < n)\n    \
+             n--;\n  while (i < n)\n    g(i);\n#endif\n}\n",
+            &[],
+        );
+        let rows = lints
+            .iter()
+            .filter(|lint| lint.rule == Rule::InfiniteLoop)
+            .map(|lint| lint.range.start_point.row)
+            .collect::<Vec<usize>>();
+        assert_eq!(rows, [6]);
+    }
+
+    #[test]
+    fn uninitialized_reads_under_a_label_are_found() {
+        let lints = lint_source(
+            "// Counts from a label\nint g(int x) {\n  if (x) goto count;\n  return 0;\ncount: {\n    int n;\n    \
+             return n + x;\n  }\n}\n",
+            &[],
+        );
+        let rows = lints
+            .iter()
+            .filter(|lint| lint.rule == Rule::UninitializedUse)
+            .map(|lint| lint.range.start_point.row)
+            .collect::<Vec<usize>>();
+        assert_eq!(rows, [6]);
+    }
+
+    #[test]
+    fn use_after_free_is_opt_in() {
+        let source = "#include <stdlib.h>\n\n// Frees twice\nvoid g(int *p) {\n  if (!p) goto out;\n  return;\n\
+                      out: {\n    free(p);\n    *p = 0;\n  }\n}\n";
+        let rows = |flags| {
+            lint_source(source, flags)
+                .iter()
+                .filter(|lint| lint.rule == Rule::UseAfterFree)
+                .map(|lint| lint.range.start_point.row)
+                .collect::<Vec<usize>>()
+        };
+        assert_eq!(rows(&["--warn-uaf"]), [8]);
+        assert!(rows(&[]).is_empty());
+    }
+
+    #[test]
+    fn dead_stores_are_opt_in() {
+        let source = "#include <stdio.h>\n\n// Reads two numbers\nint g(void) {\n  int a = 1;\n  int b = 1;\n  \
+                      a = 2;\n  b = 2;\n  scanf(\"%d\", &b);\n  return a + b;\n}\n";
+        let rows = |flags| {
+            lint_source(source, flags)
+                .iter()
+                .filter(|lint| lint.rule == Rule::DeadStore)
+                .map(|lint| lint.range.start_point.row)
+                .collect::<Vec<usize>>()
+        };
+        assert!(rows(&[]).is_empty());
+        assert_eq!(rows(&["--warn-dead-stores"]), [4]);
+    }
+
+    #[test]
+    fn function_length_breaks_down_counted_lines() {
+        let lints = lint_source(&function_with_statements(11), &[]);
+        let length = lints
+            .iter()
+            .find(|lint| lint.rule == Rule::FunctionLength)
+            .unwrap();
+        assert_eq!(
+            length.message,
+            "Function has more than 10 lines (11 lines: 9 expressions, 1 definition, 1 jump)"
+        );
+    }
+
+    /// The one-based lines a fixture is flagged on for a rule, in order
+    fn fixture_lines(name: &str, rule: Rule, flags: &[&str]) -> Vec<usize> {
+        let path = Path::new("fixtures").join(name);
+        let source = read_source(&path);
+        let mut lines = lint_file(&path, &source, &args(flags))
+            .lints
+            .iter()
+            .filter(|lint| lint.rule == rule)
+            .map(|lint| lint.range.start_point.row + 1)
+            .collect::<Vec<usize>>();
+        lines.sort();
+        lines
+    }
+
+    #[test]
+    fn implicit_int_fixture() {
+        assert_eq!(fixture_lines("implicit-int.c", Rule::ImplicitInt, &[]), [4]);
+    }
+
+    #[test]
+    fn do_while_fixture() {
+        let path = Path::new("fixtures/do-while.c");
+        let source = read_source(path);
+        let tree = c_parser().parse(&source, None).unwrap();
+        let root = tree.root_node();
+        let mut cursor = root.walk();
+        // a compact do/while is one line, a condition on its own `} while` line is another
+        let counts = root
+            .children(&mut cursor)
+            .filter(|node| node.kind() == "function_definition")
+            .map(|function| {
+                let body = function.child_by_field_name("body").unwrap();
+                count_lines_compound_statement(path, &source, body, &mut vec![], &mut vec![])
+                    .total()
+            })
+            .collect::<Vec<usize>>();
+        assert_eq!(counts, [10, 4]);
+        assert!(fixture_lines("do-while.c", Rule::FunctionLength, &[]).is_empty());
+    }
+
+    #[test]
+    fn global_declarations_fixture() {
+        assert_eq!(
+            fixture_lines("global-declarations.c", Rule::GlobalVariable, &[]),
+            [14, 15, 16, 17, 18]
+        );
+    }
+
+    #[test]
+    fn redundant_else_fixture() {
+        assert_eq!(
+            fixture_lines("redundant-else.c", Rule::RedundantElse, &[]),
+            [5, 27, 30]
+        );
+    }
+
+    #[test]
+    fn ternary_lines_fixture() {
+        let path = Path::new("fixtures/ternary-lines.c");
+        let source = read_source(path);
+        let lints = lint_file(path, &source, &args(&[])).lints;
+        let length = lints
+            .iter()
+            .find(|lint| lint.rule == Rule::FunctionLength)
+            .unwrap();
+        assert_eq!(length.range.start_point.row + 1, 4);
+        assert_eq!(
+            length.message,
+            "Function has more than 10 lines (11 lines: 5 expressions, 5 definitions, 1 jump)"
+        );
+    }
+
+    #[test]
+    fn empty_parameters_fixture() {
+        assert_eq!(
+            fixture_lines("empty-parameters.c", Rule::EmptyParameters, &[]),
+            [2, 6]
+        );
+    }
+
+    #[test]
+    fn assignment_condition_fixture() {
+        assert_eq!(
+            fixture_lines("assignment-condition.c", Rule::AssignmentCondition, &[]),
+            [6, 9, 12]
+        );
+    }
+
+    #[test]
+    fn unicode_fixture() {
+        let path = Path::new("fixtures/unicode.c");
+        let source = read_source(path);
+        // columns count the emoji and accented letters before the markers as one character each
+        let positions = lint_file(path, &source, &args(&[]))
+            .lints
+            .iter()
+            .filter(|lint| lint.rule == Rule::TodoComment)
+            .map(|lint| {
+                (
+                    lint.range.start_point.row + 1,
+                    lint.range.start_point.column + 1,
+                )
+            })
+            .collect::<Vec<(usize, usize)>>();
+        assert_eq!(positions, [(5, 31), (6, 21)]);
+    }
+
+    #[test]
+    fn return_value_fixture() {
+        assert_eq!(
+            fixture_lines("return-value.c", Rule::ReturnValue, &[]),
+            [7, 16]
+        );
+    }
+
+    #[test]
+    fn sarif_log_structure() {
+        let lints = lint_source(&function_with_statements(11), &[]);
+        assert!(lints.iter().any(|lint| lint.sublints.is_some()));
+        let log = sarif(&lints);
+
+        assert_eq!(log["version"], "2.1.0");
+        let runs = log["runs"].as_array().unwrap();
+        assert_eq!(runs.len(), 1);
+        let driver = &runs[0]["tool"]["driver"];
+        assert_eq!(driver["name"], "cse2331-linter");
+        let rules = driver["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), Rule::ALL.len());
+        assert!(rules.iter().all(|rule| rule["id"].is_string()));
+
+        let results = runs[0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), lints.len());
+        for (result, lint) in results.iter().zip(&lints) {
+            assert_eq!(result["ruleId"], lint.rule.code());
+            let region = &result["locations"][0]["physicalLocation"]["region"];
+            assert_eq!(region["startLine"], lint.range.start_point.row + 1);
+            assert_eq!(region["startColumn"], lint.range.start_point.column + 1);
+            let related = result["relatedLocations"].as_array().unwrap();
+            assert_eq!(related.len(), lint.sublints.iter().flatten().count());
+            assert!(related
+                .iter()
+                .all(|location| location["physicalLocation"]["region"]["startLine"].is_u64()));
+        }
+    }
+
+    #[test]
+    fn sublints_stay_nested() {
+        let lints = lint_source(&function_with_statements(11), &[]);
+        let long_function = lints
+            .iter()
+            .find(|lint| lint.rule == Rule::FunctionLength)
+            .unwrap();
+        let sublints = long_function.sublints.as_ref().unwrap();
+        assert_eq!(sublints.len(), 11);
+
+        let json = long_function.json();
+        let json_sublints = json["sublints"].as_array().unwrap();
+        assert_eq!(json_sublints.len(), sublints.len());
+        for (json_sublint, sublint) in json_sublints.iter().zip(sublints) {
+            assert_eq!(json_sublint["message"], sublint.message);
+            assert_eq!(json_sublint["line"], sublint.range.start_point.row + 1);
+            assert_eq!(json_sublint["sublints"], json!([]));
+        }
+
+        let log = sarif(std::slice::from_ref(long_function));
+        let related = log["runs"][0]["results"][0]["relatedLocations"]
+            .as_array()
+            .unwrap();
+        assert_eq!(related.len(), sublints.len());
+        for (location, sublint) in related.iter().zip(sublints) {
+            assert_eq!(location["message"]["text"], sublint.message);
+            assert_eq!(
+                location["physicalLocation"]["region"]["startLine"],
+                sublint.range.start_point.row + 1
+            );
+        }
+    }
+
+    /// A string comparison lint on line 3 of `file` with markup characters in its message and text
+    fn markup_lint(file: &Path) -> Lint<'_> {
+        Lint {
+            rule: Rule::StringComparison,
+            file,
+            range: Range {
+                start_byte: 40,
+                end_byte: 46,
+                start_point: Point::new(2, 6),
+                end_point: Point::new(2, 12),
+            },
+            text: "  if (a == \"<&>\") {".to_string(),
+            message: "Compare \"a\" & \"<&>\" with strcmp".to_string(),
+            sublints: None,
+        }
+    }
+
+    /// Checks that every tag is closed in order and that `<`, `>`, `&`, and `"` only appear as
+    /// markup or escaped, in text as well as attribute values
+    fn assert_well_formed(xml: &str) {
+        let body = xml
+            .strip_prefix("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")
+            .unwrap();
+        let entity = "&(?:amp|lt|gt|quot|apos);";
+        let tag_regex = Regex::new(&format!(
+            r#"<(/?)([A-Za-z]+)(?:\s+[A-Za-z]+="(?:[^"<>&]|{entity})*")*\s*(/?)>"#
+        ))
+        .unwrap();
+        let text_regex = Regex::new(&format!("^(?:[^<>&]|{entity})*$")).unwrap();
+        let mut open = vec![];
+        let mut end = 0;
+        for tag in tag_regex.captures_iter(body) {
+            let whole = tag.get(0).unwrap();
+            let text = &body[end..whole.start()];
+            assert!(text_regex.is_match(text), "malformed text `{text}`");
+            end = whole.end();
+            match (&tag[1], &tag[3]) {
+                ("/", _) => assert_eq!(open.pop().as_deref(), Some(&tag[2])),
+                (_, "/") => {}
+                _ => open.push(tag[2].to_string()),
+            }
+        }
+        assert!(
+            body[end..].trim().is_empty(),
+            "malformed text after the root"
+        );
+        assert!(open.is_empty(), "unclosed {open:?}");
+    }
+
+    #[test]
+    fn junit_is_well_formed() {
+        let files = [PathBuf::from("dirty.c"), PathBuf::from("clean.c")];
+        let xml = junit(&files, &[markup_lint(&files[0])]);
+        assert_well_formed(&xml);
+        assert!(xml.contains(
+            "<failure message=\"Compare &quot;a&quot; &amp; &quot;&lt;&amp;&gt;&quot; with strcmp\""
+        ));
+        assert!(xml.contains("<testsuite name=\"dirty.c\" tests=\"1\" failures=\"1\">"));
+        assert!(xml.contains(
+            "<testsuite name=\"clean.c\" tests=\"1\" failures=\"0\">\n    \
+             <testcase name=\"lint\" classname=\"clean.c\"/>\n  </testsuite>"
+        ));
+    }
+
+    #[test]
+    fn checkstyle_is_well_formed() {
+        let files = [PathBuf::from("dirty.c"), PathBuf::from("clean.c")];
+        let xml = checkstyle(&files, &[markup_lint(&files[0])]);
+        assert_well_formed(&xml);
+        assert!(xml.contains(
+            "<error line=\"3\" column=\"7\" severity=\"error\" \
+             message=\"Compare &quot;a&quot; &amp; &quot;&lt;&amp;&gt;&quot; with strcmp\" \
+             source=\"string-comparison\"/>"
+        ));
+    }
+
+    #[test]
+    fn checkstyle_lists_clean_files() {
+        let files = [PathBuf::from("dirty.c"), PathBuf::from("clean.c")];
+        let xml = checkstyle(&files, &[markup_lint(&files[0])]);
+        assert!(xml.contains("<file name=\"dirty.c\">\n"));
+        assert!(xml.contains("<file name=\"clean.c\"/>\n"));
+
+        let xml = checkstyle(&files, &[]);
+        assert_well_formed(&xml);
+        assert_eq!(xml.matches("<file ").count(), 2);
+        assert!(!xml.contains("<error"));
+    }
+
+    #[test]
+    fn include_spellings_collapse() {
+        let dir = temp_dir(
+            "include-spellings",
+            &[
+                ("main.c", "#include \"./util.h\"\n#include \"util.h\"\n"),
+                ("util.h", "int twice(int n);\n"),
+            ],
+        );
+        let files = discover_files(dir.join("main.c"), &args(&[]));
+        assert_eq!(files.len(), 2);
+        assert!(files.contains(&canonicalize(dir.join("main.c"))));
+        assert!(files.contains(&canonicalize(dir.join("util.h"))));
+    }
+
+    #[test]
+    fn include_cycle_terminates() {
+        let dir = temp_dir(
+            "include-cycle",
+            &[
+                ("m.c", "#include \"a.h\"\n"),
+                ("a.h", "#include \"b.h\"\n"),
+                ("b.h", "#include \"a.h\"\n"),
+            ],
+        );
+        let files = discover_files(dir.join("m.c"), &args(&[]));
+        let expected = ["m.c", "a.h", "b.h"]
+            .map(|file| canonicalize(dir.join(file)))
+            .into_iter()
+            .collect::<HashSet<PathBuf>>();
+        assert_eq!(files, expected);
+    }
+}
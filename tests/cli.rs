@@ -0,0 +1,238 @@
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeSet,
+    fs,
+    process::{Command, Output},
+};
+
+fn linter(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cse2421-linter"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap()
+}
+
+// `misplaced-jump.c` has lints of error severity, which fail the run by default
+const FIXTURE: &str = "fixtures/misplaced-jump.c";
+
+#[test]
+fn fails_on_errors_by_default() {
+    let output = linter(&[FIXTURE]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn relative_exit_still_reports() {
+    for format in [
+        ["--error-format", "github"],
+        ["--format", "sarif"],
+        ["--format", "json"],
+    ] {
+        let output = linter(&[format[0], format[1], "--relative-exit", FIXTURE]);
+        assert_eq!(output.status.code(), Some(0), "{format:?}");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(!stdout.trim().is_empty(), "{format:?}");
+        if format[1] == "github" {
+            assert!(
+                stdout
+                    .lines()
+                    .all(|line| line.starts_with("::error ") || line.starts_with("::warning ")),
+                "{stdout}"
+            );
+        } else {
+            assert!(stdout.contains("misplaced-jump"), "{format:?}");
+        }
+    }
+}
+
+#[test]
+fn summary_schema() {
+    let codes = String::from_utf8(linter(&["--list-rules"]).stdout)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.starts_with(' '))
+        .map(|line| line.split_whitespace().next().unwrap().to_string())
+        .collect::<BTreeSet<String>>();
+    for format in ["text", "json"] {
+        let path = std::env::temp_dir().join(format!(
+            "cse2421-linter-summary-{format}-{}.json",
+            std::process::id()
+        ));
+        let output = linter(&[
+            "--format",
+            format,
+            "--summary-json",
+            path.to_str().unwrap(),
+            FIXTURE,
+        ]);
+        assert_eq!(output.status.code(), Some(1));
+        let summary: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let keys = |value: &Value| {
+            value
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect::<BTreeSet<String>>()
+        };
+        assert_eq!(
+            keys(&summary),
+            BTreeSet::from(["files", "passed", "rules", "severities", "total"].map(String::from))
+        );
+        assert_eq!(summary["total"], 2);
+        assert_eq!(summary["passed"], false);
+        assert_eq!(summary["files"], json!({ FIXTURE: 2 }));
+        assert_eq!(keys(&summary["rules"]), codes);
+        assert_eq!(summary["rules"]["misplaced-jump"], 2);
+        assert_eq!(summary["severities"], json!({ "error": 2, "warning": 0 }));
+    }
+}
+
+#[test]
+fn unreadable_files_are_reported() {
+    for flags in [&[][..], &["--print-includes"], &["--no-recurse-includes"]] {
+        let mut args = flags.to_vec();
+        args.push("fixtures/missing.c");
+        let output = linter(&args);
+        assert_eq!(output.status.code(), Some(2), "{flags:?}");
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(
+            stderr.starts_with("Failed to read `fixtures/missing.c`"),
+            "{stderr}"
+        );
+    }
+}
+
+#[test]
+fn only_changed_lines_ignores_diff_prefix_config() {
+    let dir = std::env::temp_dir().join(format!(
+        "cse2421-linter-only-changed-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(&dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?}");
+    };
+    git(&["init", "-q"]);
+    git(&["config", "diff.noprefix", "true"]);
+    fs::write(dir.join("main.c"), "int first;\n").unwrap();
+    git(&["add", "main.c"]);
+    git(&["commit", "-q", "-m", "first"]);
+    fs::write(dir.join("main.c"), "int first;\nint second;\n").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cse2421-linter"))
+        .args(["--only-changed", "HEAD", "--only-changed-lines"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "main.c:2:1 Global variable `int second;`\n");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn config_print_merges_config_files() {
+    let dir = std::env::temp_dir().join(format!(
+        "cse2421-linter-config-print-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(".cse2331lint.toml"),
+        "[rules]\nglobal-variable = false\ntodo-comment = true\n",
+    )
+    .unwrap();
+    let enabled = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_cse2421-linter"))
+            .arg("--config-print")
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        let config: Value = serde_json::from_slice(&output.stdout).unwrap();
+        config["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|rule| {
+                ["global-variable", "todo-comment"].contains(&rule["code"].as_str().unwrap())
+            })
+            .map(|rule| {
+                (
+                    rule["code"].as_str().unwrap().to_string(),
+                    rule["enabled"].as_bool().unwrap(),
+                )
+            })
+            .collect::<Vec<(String, bool)>>()
+    };
+
+    // the file turns one rule off, and the command line wins over the file turning one on
+    assert_eq!(
+        enabled(&[]),
+        [
+            ("global-variable".to_string(), false),
+            ("todo-comment".to_string(), true)
+        ]
+    );
+    assert_eq!(
+        enabled(&["--disable", "todo-comment"]),
+        [
+            ("global-variable".to_string(), false),
+            ("todo-comment".to_string(), false)
+        ]
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn config_cannot_enable_rules_left_off() {
+    let dir = std::env::temp_dir().join(format!(
+        "cse2421-linter-config-enable-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join(".cse2331lint.toml"),
+        "[rules]\nglobal-variable = true\nuse-after-free = true\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("main.c"),
+        "#include <stdlib.h>\n\n// Frees too early\nvoid clear(int *p) {\n  free(p);\n  *p = 0;\n}\n",
+    )
+    .unwrap();
+    let lint = |args: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_cse2421-linter"))
+            .args(args)
+            .arg("main.c")
+            .current_dir(&dir)
+            .output()
+            .unwrap();
+        (
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap(),
+        )
+    };
+
+    let (stdout, stderr) = lint(&[]);
+    assert!(!stdout.contains("use-after-free"), "{stdout}");
+    assert!(stderr.contains("`use-after-free = true`"), "{stderr}");
+    assert!(!stderr.contains("global-variable"), "{stderr}");
+
+    let (stdout, stderr) = lint(&["--warn-uaf"]);
+    assert!(stdout.contains("main.c:6:4"), "{stdout}");
+    assert!(stderr.is_empty(), "{stderr}");
+    fs::remove_dir_all(&dir).unwrap();
+}